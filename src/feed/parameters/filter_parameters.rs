@@ -1,6 +1,108 @@
 use serde::{Deserialize, Serialize};
 use serde_json::{Value,Map};
 
+#[allow(unused)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WindowSubParameters {
+    pub function: String,
+    pub column: Option<String>,
+    pub periods: Option<i64>,
+    pub alias: Option<String>
+}
+
+#[allow(unused)]
+impl WindowSubParameters {
+    pub fn as_map(&self) -> Map<String, Value> {
+        let mut params: Map<String, Value> = Map::with_capacity(4);
+        params.insert(
+            "function".into(),
+            Value::from(self.function.clone())
+        );
+        if let Some(column) = self.column.clone() {
+            params.insert(
+                "column".into(),
+                Value::from(column)
+            );
+        }
+        if let Some(periods) = self.periods {
+            params.insert(
+                "periods".into(),
+                Value::from(periods)
+            );
+        }
+        if let Some(alias) = self.alias.clone() {
+            params.insert(
+                "alias".into(),
+                Value::from(alias)
+            );
+        }
+
+        params
+    }
+}
+
+#[allow(unused)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AggregationSubParameters {
+    pub column: String,
+    pub function: String,
+    pub alias: Option<String>,
+    pub time_column: Option<String>
+}
+
+#[allow(unused)]
+impl AggregationSubParameters {
+    pub fn as_map(&self) -> Map<String, Value> {
+        let mut params: Map<String, Value> = Map::with_capacity(4);
+        params.insert(
+            "column".into(),
+            Value::from(self.column.clone())
+        );
+        params.insert(
+            "function".into(),
+            Value::from(self.function.clone())
+        );
+        if let Some(alias) = self.alias.clone() {
+            params.insert(
+                "alias".into(),
+                Value::from(alias)
+            );
+        }
+        if let Some(time_column) = self.time_column.clone() {
+            params.insert(
+                "time_column".into(),
+                Value::from(time_column)
+            );
+        }
+
+        params
+    }
+}
+
+#[allow(unused)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CaseBranchSubParameters {
+    pub when: Map<String, Value>,
+    pub then: Value
+}
+
+#[allow(unused)]
+impl CaseBranchSubParameters {
+    pub fn as_map(&self) -> Map<String, Value> {
+        let mut params: Map<String, Value> = Map::with_capacity(2);
+        params.insert(
+            "when".into(),
+            Value::from(self.when.clone())
+        );
+        params.insert(
+            "then".into(),
+            self.then.clone()
+        );
+
+        params
+    }
+}
+
 #[allow(unused)]
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct FilterSubParameters {
@@ -8,13 +110,94 @@ pub struct FilterSubParameters {
     pub columns: Option<Vec<String>>,
     pub filter: Option<String>,
     pub value: Option<Value>,
-    pub r#type: Option<String>
+    pub r#type: Option<String>,
+
+    // Window
+    pub by: Option<Vec<String>>,
+    pub order_by: Option<String>,
+    pub windows: Option<Vec<WindowSubParameters>>,
+
+    // Resample / fill_null
+    pub time_column: Option<String>,
+    pub every: Option<Value>,
+    pub strategy: Option<String>,
+
+    // Gaps / staleness
+    pub threshold: Option<Value>,
+    pub reference: Option<Value>,
+    pub time_unit: Option<String>,
+
+    // Sessionize
+    pub timeout: Option<Value>,
+    pub aggregate: Option<bool>,
+    pub aggregations: Option<Vec<AggregationSubParameters>>,
+
+    // OHLC
+    pub period: Option<Value>,
+    pub offset: Option<Value>,
+    pub weight_column: Option<String>,
+    pub label: Option<String>,
+    pub closed: Option<String>,
+    pub start_by: Option<String>,
+
+    // EWM
+    pub span: Option<usize>,
+    pub half_life: Option<f64>,
+    pub alpha: Option<f64>,
+    pub com: Option<f64>,
+    pub adjust: Option<bool>,
+    pub min_periods: Option<usize>,
+    pub ignore_nulls: Option<bool>,
+    pub suffix: Option<String>,
+
+    // Regression
+    pub target: Option<String>,
+    pub features: Option<Vec<String>>,
+    pub append_fitted: Option<bool>,
+
+    // Scale / one_hot / categorize
+    pub method: Option<String>,
+    pub statistics: Option<Value>,
+    pub categories: Option<Vec<String>>,
+    pub drop_original: Option<bool>,
+
+    // Sample / split
+    pub n: Option<u64>,
+    pub fraction: Option<f64>,
+    pub seed: Option<u64>,
+    pub with_replacement: Option<bool>,
+    pub ratios: Option<Vec<f64>>,
+    pub labels: Option<Vec<String>>,
+    pub alias: Option<String>,
+
+    // Unnest / json_decode
+    pub separator: Option<String>,
+    pub schema: Option<Map<String, Value>>,
+    pub infer_schema_length: Option<usize>,
+
+    // Temporal
+    pub format: Option<String>,
+    pub replace_time_zone: Option<String>,
+    pub time_zone: Option<String>,
+    pub truncate: Option<String>,
+    pub round: Option<String>,
+    pub extract: Option<Vec<String>>,
+
+    // Mask
+    pub salt_env: Option<String>,
+    pub length: Option<usize>,
+    pub pattern: Option<String>,
+    pub replacement: Option<String>,
+
+    // Case
+    pub branches: Option<Vec<CaseBranchSubParameters>>,
+    pub otherwise: Option<Value>
 }
 
 #[allow(unused)]
 impl FilterSubParameters {
     pub fn as_map(&self) -> Map<String, Value> {
-        let mut params: Map<String, Value> = Map::with_capacity(5);
+        let mut params: Map<String, Value> = Map::with_capacity(60);
         if let Some(column) = self.column.clone() {
             params.insert(
                 "column".into(),
@@ -36,7 +219,7 @@ impl FilterSubParameters {
         if let Some(value) = self.value.clone() {
             params.insert(
                 "value".into(),
-                Value::from(value)
+                value
             );
         }
         if let Some(r#type) = self.r#type.clone() {
@@ -45,6 +228,351 @@ impl FilterSubParameters {
                 Value::from(r#type)
             );
         }
+        if let Some(by) = self.by.clone() {
+            params.insert(
+                "by".into(),
+                Value::from(by)
+            );
+        }
+        if let Some(order_by) = self.order_by.clone() {
+            params.insert(
+                "order_by".into(),
+                Value::from(order_by)
+            );
+        }
+        if let Some(windows) = self.windows.clone() {
+            params.insert(
+                "windows".into(),
+                Value::from(
+                    windows
+                    .iter()
+                    .map(|w| Value::from(w.as_map()))
+                    .collect::<Vec<Value>>()
+                )
+            );
+        }
+        if let Some(time_column) = self.time_column.clone() {
+            params.insert(
+                "time_column".into(),
+                Value::from(time_column)
+            );
+        }
+        if let Some(every) = self.every.clone() {
+            params.insert(
+                "every".into(),
+                every
+            );
+        }
+        if let Some(strategy) = self.strategy.clone() {
+            params.insert(
+                "strategy".into(),
+                Value::from(strategy)
+            );
+        }
+        if let Some(threshold) = self.threshold.clone() {
+            params.insert(
+                "threshold".into(),
+                threshold
+            );
+        }
+        if let Some(reference) = self.reference.clone() {
+            params.insert(
+                "reference".into(),
+                reference
+            );
+        }
+        if let Some(time_unit) = self.time_unit.clone() {
+            params.insert(
+                "time_unit".into(),
+                Value::from(time_unit)
+            );
+        }
+        if let Some(timeout) = self.timeout.clone() {
+            params.insert(
+                "timeout".into(),
+                timeout
+            );
+        }
+        if let Some(aggregate) = self.aggregate {
+            params.insert(
+                "aggregate".into(),
+                Value::from(aggregate)
+            );
+        }
+        if let Some(aggregations) = self.aggregations.clone() {
+            params.insert(
+                "aggregations".into(),
+                Value::from(
+                    aggregations
+                    .iter()
+                    .map(|a| Value::from(a.as_map()))
+                    .collect::<Vec<Value>>()
+                )
+            );
+        }
+        if let Some(period) = self.period.clone() {
+            params.insert(
+                "period".into(),
+                period
+            );
+        }
+        if let Some(offset) = self.offset.clone() {
+            params.insert(
+                "offset".into(),
+                offset
+            );
+        }
+        if let Some(weight_column) = self.weight_column.clone() {
+            params.insert(
+                "weight_column".into(),
+                Value::from(weight_column)
+            );
+        }
+        if let Some(label) = self.label.clone() {
+            params.insert(
+                "label".into(),
+                Value::from(label)
+            );
+        }
+        if let Some(closed) = self.closed.clone() {
+            params.insert(
+                "closed".into(),
+                Value::from(closed)
+            );
+        }
+        if let Some(start_by) = self.start_by.clone() {
+            params.insert(
+                "start_by".into(),
+                Value::from(start_by)
+            );
+        }
+        if let Some(span) = self.span {
+            params.insert(
+                "span".into(),
+                Value::from(span)
+            );
+        }
+        if let Some(half_life) = self.half_life {
+            params.insert(
+                "half_life".into(),
+                Value::from(half_life)
+            );
+        }
+        if let Some(alpha) = self.alpha {
+            params.insert(
+                "alpha".into(),
+                Value::from(alpha)
+            );
+        }
+        if let Some(com) = self.com {
+            params.insert(
+                "com".into(),
+                Value::from(com)
+            );
+        }
+        if let Some(adjust) = self.adjust {
+            params.insert(
+                "adjust".into(),
+                Value::from(adjust)
+            );
+        }
+        if let Some(min_periods) = self.min_periods {
+            params.insert(
+                "min_periods".into(),
+                Value::from(min_periods)
+            );
+        }
+        if let Some(ignore_nulls) = self.ignore_nulls {
+            params.insert(
+                "ignore_nulls".into(),
+                Value::from(ignore_nulls)
+            );
+        }
+        if let Some(suffix) = self.suffix.clone() {
+            params.insert(
+                "suffix".into(),
+                Value::from(suffix)
+            );
+        }
+        if let Some(target) = self.target.clone() {
+            params.insert(
+                "target".into(),
+                Value::from(target)
+            );
+        }
+        if let Some(features) = self.features.clone() {
+            params.insert(
+                "features".into(),
+                Value::from(features)
+            );
+        }
+        if let Some(append_fitted) = self.append_fitted {
+            params.insert(
+                "append_fitted".into(),
+                Value::from(append_fitted)
+            );
+        }
+        if let Some(method) = self.method.clone() {
+            params.insert(
+                "method".into(),
+                Value::from(method)
+            );
+        }
+        if let Some(statistics) = self.statistics.clone() {
+            params.insert(
+                "statistics".into(),
+                statistics
+            );
+        }
+        if let Some(categories) = self.categories.clone() {
+            params.insert(
+                "categories".into(),
+                Value::from(categories)
+            );
+        }
+        if let Some(drop_original) = self.drop_original {
+            params.insert(
+                "drop_original".into(),
+                Value::from(drop_original)
+            );
+        }
+        if let Some(n) = self.n {
+            params.insert(
+                "n".into(),
+                Value::from(n)
+            );
+        }
+        if let Some(fraction) = self.fraction {
+            params.insert(
+                "fraction".into(),
+                Value::from(fraction)
+            );
+        }
+        if let Some(seed) = self.seed {
+            params.insert(
+                "seed".into(),
+                Value::from(seed)
+            );
+        }
+        if let Some(with_replacement) = self.with_replacement {
+            params.insert(
+                "with_replacement".into(),
+                Value::from(with_replacement)
+            );
+        }
+        if let Some(ratios) = self.ratios.clone() {
+            params.insert(
+                "ratios".into(),
+                Value::from(ratios)
+            );
+        }
+        if let Some(labels) = self.labels.clone() {
+            params.insert(
+                "labels".into(),
+                Value::from(labels)
+            );
+        }
+        if let Some(alias) = self.alias.clone() {
+            params.insert(
+                "alias".into(),
+                Value::from(alias)
+            );
+        }
+        if let Some(separator) = self.separator.clone() {
+            params.insert(
+                "separator".into(),
+                Value::from(separator)
+            );
+        }
+        if let Some(schema) = self.schema.clone() {
+            params.insert(
+                "schema".into(),
+                Value::from(schema)
+            );
+        }
+        if let Some(infer_schema_length) = self.infer_schema_length {
+            params.insert(
+                "infer_schema_length".into(),
+                Value::from(infer_schema_length)
+            );
+        }
+        if let Some(format) = self.format.clone() {
+            params.insert(
+                "format".into(),
+                Value::from(format)
+            );
+        }
+        if let Some(replace_time_zone) = self.replace_time_zone.clone() {
+            params.insert(
+                "replace_time_zone".into(),
+                Value::from(replace_time_zone)
+            );
+        }
+        if let Some(time_zone) = self.time_zone.clone() {
+            params.insert(
+                "time_zone".into(),
+                Value::from(time_zone)
+            );
+        }
+        if let Some(truncate) = self.truncate.clone() {
+            params.insert(
+                "truncate".into(),
+                Value::from(truncate)
+            );
+        }
+        if let Some(round) = self.round.clone() {
+            params.insert(
+                "round".into(),
+                Value::from(round)
+            );
+        }
+        if let Some(extract) = self.extract.clone() {
+            params.insert(
+                "extract".into(),
+                Value::from(extract)
+            );
+        }
+        if let Some(salt_env) = self.salt_env.clone() {
+            params.insert(
+                "salt_env".into(),
+                Value::from(salt_env)
+            );
+        }
+        if let Some(length) = self.length {
+            params.insert(
+                "length".into(),
+                Value::from(length)
+            );
+        }
+        if let Some(pattern) = self.pattern.clone() {
+            params.insert(
+                "pattern".into(),
+                Value::from(pattern)
+            );
+        }
+        if let Some(replacement) = self.replacement.clone() {
+            params.insert(
+                "replacement".into(),
+                Value::from(replacement)
+            );
+        }
+        if let Some(branches) = self.branches.clone() {
+            params.insert(
+                "branches".into(),
+                Value::from(
+                    branches
+                    .iter()
+                    .map(|b| Value::from(b.as_map()))
+                    .collect::<Vec<Value>>()
+                )
+            );
+        }
+        if let Some(otherwise) = self.otherwise.clone() {
+            params.insert(
+                "otherwise".into(),
+                otherwise
+            );
+        }
 
 
         return params;
@@ -56,7 +584,62 @@ impl FilterSubParameters {
             columns: None,
             filter: None,
             value: None,
-            r#type: None
+            r#type: None,
+            by: None,
+            order_by: None,
+            windows: None,
+            time_column: None,
+            every: None,
+            strategy: None,
+            threshold: None,
+            reference: None,
+            time_unit: None,
+            timeout: None,
+            aggregate: None,
+            aggregations: None,
+            period: None,
+            offset: None,
+            weight_column: None,
+            label: None,
+            closed: None,
+            start_by: None,
+            span: None,
+            half_life: None,
+            alpha: None,
+            com: None,
+            adjust: None,
+            min_periods: None,
+            ignore_nulls: None,
+            suffix: None,
+            target: None,
+            features: None,
+            append_fitted: None,
+            method: None,
+            statistics: None,
+            categories: None,
+            drop_original: None,
+            n: None,
+            fraction: None,
+            seed: None,
+            with_replacement: None,
+            ratios: None,
+            labels: None,
+            alias: None,
+            separator: None,
+            schema: None,
+            infer_schema_length: None,
+            format: None,
+            replace_time_zone: None,
+            time_zone: None,
+            truncate: None,
+            round: None,
+            extract: None,
+            salt_env: None,
+            length: None,
+            pattern: None,
+            replacement: None,
+            branches: None,
+            otherwise: None
         }
     }
 }
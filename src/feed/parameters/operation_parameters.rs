@@ -13,12 +13,32 @@ pub struct OperationParameters {
     pub suffix: Option<String>,
     pub validation: Option<String>,
     pub join_nulls: Option<bool>,
-    pub coalesce: Option<String>
+    pub coalesce: Option<String>,
+
+    // Cross correlation
+    pub left_column: Option<String>,
+    pub right_column: Option<String>,
+    pub method: Option<String>,
+    pub min_lag: Option<i64>,
+    pub max_lag: Option<i64>,
+
+    // Set operations
+    pub on: Option<Vec<String>>,
+
+    // Reconcile
+    pub columns: Option<Vec<String>>,
+    pub tolerance: Option<f64>,
+    pub tolerances: Option<Map<String, Value>>,
+
+    // Fuzzy join
+    pub by: Option<Vec<String>>,
+    pub threshold: Option<f64>,
+    pub ignore_case: Option<bool>
 }
 
 impl OperationParameters {
     pub fn as_map(&self) -> Map<String, Value> {
-        let mut params: Map<String, Value> = Map::with_capacity(9);
+        let mut params: Map<String, Value> = Map::with_capacity(21);
         if let Some(left_on) = self.left_on.clone() {
             params.insert(
                 "left_on".into(),
@@ -73,6 +93,78 @@ impl OperationParameters {
                 Value::from(coalesce)
             );
         }
+        if let Some(left_column) = self.left_column.clone() {
+            params.insert(
+                "left_column".into(),
+                Value::from(left_column)
+            );
+        }
+        if let Some(right_column) = self.right_column.clone() {
+            params.insert(
+                "right_column".into(),
+                Value::from(right_column)
+            );
+        }
+        if let Some(method) = self.method.clone() {
+            params.insert(
+                "method".into(),
+                Value::from(method)
+            );
+        }
+        if let Some(min_lag) = self.min_lag {
+            params.insert(
+                "min_lag".into(),
+                Value::from(min_lag)
+            );
+        }
+        if let Some(max_lag) = self.max_lag {
+            params.insert(
+                "max_lag".into(),
+                Value::from(max_lag)
+            );
+        }
+        if let Some(on) = self.on.clone() {
+            params.insert(
+                "on".into(),
+                Value::from(on)
+            );
+        }
+        if let Some(columns) = self.columns.clone() {
+            params.insert(
+                "columns".into(),
+                Value::from(columns)
+            );
+        }
+        if let Some(tolerance) = self.tolerance {
+            params.insert(
+                "tolerance".into(),
+                Value::from(tolerance)
+            );
+        }
+        if let Some(tolerances) = self.tolerances.clone() {
+            params.insert(
+                "tolerances".into(),
+                Value::from(tolerances)
+            );
+        }
+        if let Some(by) = self.by.clone() {
+            params.insert(
+                "by".into(),
+                Value::from(by)
+            );
+        }
+        if let Some(threshold) = self.threshold {
+            params.insert(
+                "threshold".into(),
+                Value::from(threshold)
+            );
+        }
+        if let Some(ignore_case) = self.ignore_case {
+            params.insert(
+                "ignore_case".into(),
+                Value::from(ignore_case)
+            );
+        }
 
 
         return params;
@@ -88,7 +180,19 @@ impl OperationParameters {
             suffix: None,
             validation: None,
             join_nulls: None,
-            coalesce: None
+            coalesce: None,
+            left_column: None,
+            right_column: None,
+            method: None,
+            min_lag: None,
+            max_lag: None,
+            on: None,
+            columns: None,
+            tolerance: None,
+            tolerances: None,
+            by: None,
+            threshold: None,
+            ignore_case: None
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,53 @@
+use polars::{error::ErrString, prelude::*};
+
+use serde::{Deserialize, Serialize};
+
+use super::time_weighted_mean::time_weighted_mean_expr;
+
+#[allow(unused)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AggregationRawParameters {
+    pub column: String,
+    pub function: String,
+    pub alias: Option<String>,
+    pub time_column: Option<String>
+}
+
+
+/// Builds the aggregation expression described by `aggregation`, aliased to
+/// `{column}_{function}` unless an alias is given.
+pub fn aggregation_expr(
+    aggregation: &AggregationRawParameters
+) -> Result<Expr, Box<dyn std::error::Error>> {
+    let column = aggregation.column.as_str();
+    let function = aggregation.function.as_str();
+
+    let expr: Expr = match function {
+        "sum" => col(column).sum(),
+        "mean" => col(column).mean(),
+        "median" => col(column).median(),
+        "min" => col(column).min(),
+        "max" => col(column).max(),
+        "first" => col(column).first(),
+        "last" => col(column).last(),
+        "count" => col(column).count(),
+        "n_unique" => col(column).n_unique(),
+        "std" => col(column).std(1),
+        "var" => col(column).var(1),
+        "time_weighted_mean" => {
+            match &aggregation.time_column {
+                Some(time_column) => time_weighted_mean_expr(column, time_column),
+                None => {
+                    return Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("time_column is expected for aggregation 'time_weighted_mean'"))))
+                }
+            }
+        },
+        _ => {
+            return Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("Invalid aggregation function"))))
+        }
+    };
+
+    let alias = aggregation.alias.clone().unwrap_or(format!("{column}_{function}"));
+
+    Ok(expr.alias(alias))
+}
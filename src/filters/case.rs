@@ -0,0 +1,112 @@
+use polars::prelude::*;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map,Value};
+
+use super::fill_null::literal_from_value;
+use super::filter_value::filter_value_expr;
+
+#[allow(unused)]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CasePredicateRawParameters {
+    pub column: String,
+    pub filter: String,
+    pub value: Value,
+    pub r#type: String
+}
+
+#[allow(unused)]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CaseBranchRawParameters {
+    pub when: CasePredicateRawParameters,
+    pub then: Value
+}
+
+#[allow(unused)]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CaseRawParameters {
+    pub branches: Vec<CaseBranchRawParameters>,
+    pub otherwise: Option<Value>,
+    pub alias: String
+}
+
+impl CaseRawParameters {
+    pub fn from_map(map: &Map<String, Value>) -> Result<Self, serde_json::Error> {
+        let value = Value::Object(map.clone());
+        serde_json::from_value(value)
+    }
+}
+
+
+/// A branch output is either a literal, or `{"column": name}` to take the
+/// value of another column.
+fn output_expr(
+    value: &Value
+) -> Result<Expr, Box<dyn std::error::Error>> {
+    if let Some(column) = value.as_object().and_then(|object| object.get("column")).and_then(|column| column.as_str()) {
+        return Ok(col(column))
+    }
+
+    literal_from_value(value)
+}
+
+
+/// Appends an `alias` column taking the `then` output of the first branch
+/// whose `when` predicate holds, or `otherwise` (null by default).
+///
+/// Predicates use the `filter_value` representation: `column`, `filter`
+/// (gte, gt, lte, lt or eq), `value` and `type`.
+pub fn case(
+    lf: LazyFrame,
+    parameters: &CaseRawParameters
+) -> Result<LazyFrame, Box<dyn std::error::Error>> {
+    let mut expr: Expr = match &parameters.otherwise {
+        Some(otherwise) => output_expr(otherwise)?,
+        None => lit(NULL)
+    };
+
+    for branch in parameters.branches.iter().rev() {
+        let predicate = filter_value_expr(
+            &branch.when.column,
+            &branch.when.filter,
+            &branch.when.value,
+            &branch.when.r#type
+        )?;
+        expr = when(predicate).then(output_expr(&branch.then)?).otherwise(expr);
+    }
+
+    Ok(lf.with_columns([expr.alias(parameters.alias.as_str())]))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parameters(value: Value, r#type: &str) -> CaseRawParameters {
+        CaseRawParameters::from_map(
+            serde_json::json!({
+                "branches": [{"when": {"column": "x", "filter": "gte", "value": value, "type": r#type}, "then": "high"}],
+                "otherwise": "low",
+                "alias": "band"
+            })
+            .as_object()
+            .unwrap()
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn case_rejects_values_not_matching_their_type() {
+        let lf = df!("x" => [1i64, 5]).unwrap().lazy();
+        assert!(case(lf.clone(), &parameters(Value::from("abc"), "int")).is_err());
+        assert!(case(lf.clone(), &parameters(Value::from(1.5), "int")).is_err());
+        assert!(case(lf.clone(), &parameters(Value::from(-1), "uint")).is_err());
+        assert!(case(lf.clone(), &parameters(Value::from("abc"), "float")).is_err());
+        assert!(case(lf.clone(), &parameters(Value::from(3), "date")).is_err());
+
+        let df = case(lf, &parameters(Value::from(3), "int")).unwrap().collect().unwrap();
+        let band: Vec<Option<&str>> = df.column("band").unwrap().str().unwrap().into_iter().collect();
+        assert_eq!(band, vec![Some("low"), Some("high")]);
+    }
+}
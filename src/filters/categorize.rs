@@ -0,0 +1,40 @@
+use polars::prelude::*;
+use polars::export::arrow::array::Utf8ViewArray;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map,Value};
+
+#[allow(unused)]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CategorizeRawParameters {
+    pub columns: Vec<String>,
+    pub categories: Option<Vec<String>>
+}
+
+impl CategorizeRawParameters {
+    pub fn from_map(map: &Map<String, Value>) -> Result<Self, serde_json::Error> {
+        let value = Value::Object(map.clone());
+        serde_json::from_value(value)
+    }
+}
+
+
+/// Casts string `columns` to an `Enum` holding exactly `categories`, in that
+/// order, so every export shares the same encoding. Values outside the list
+/// become null. Without `categories`, columns become plain `Categorical`.
+pub fn categorize(
+    lf: LazyFrame,
+    parameters: &CategorizeRawParameters
+) -> LazyFrame {
+    let dtype: DataType = match &parameters.categories {
+        Some(categories) => create_enum_dtype(Utf8ViewArray::from_slice_values(categories)),
+        None => DataType::Categorical(None, CategoricalOrdering::Physical)
+    };
+
+    lf.with_columns(
+        parameters.columns
+            .iter()
+            .map(|column| col(column).cast(dtype.clone()))
+            .collect::<Vec<Expr>>()
+    )
+}
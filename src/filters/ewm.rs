@@ -0,0 +1,89 @@
+use polars::{error::ErrString, prelude::*};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map,Value};
+
+#[allow(unused)]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EwmRawParameters {
+    pub columns: Vec<String>,
+    pub span: Option<usize>,
+    pub half_life: Option<f64>,
+    pub alpha: Option<f64>,
+    pub com: Option<f64>,
+    pub adjust: Option<bool>,
+    pub min_periods: Option<usize>,
+    pub ignore_nulls: Option<bool>,
+    pub by: Option<Vec<String>>,
+    pub order_by: Option<String>,
+    pub suffix: Option<String>
+}
+
+impl EwmRawParameters {
+    pub fn from_map(map: &Map<String, Value>) -> Result<Self, serde_json::Error> {
+        let value = Value::Object(map.clone());
+        serde_json::from_value(value)
+    }
+}
+
+
+fn ewm_options(
+    parameters: &EwmRawParameters
+) -> Result<EWMOptions, Box<dyn std::error::Error>> {
+    let mut options = EWMOptions::default();
+
+    options = match (parameters.span, parameters.half_life, parameters.alpha, parameters.com) {
+        (Some(span), None, None, None) => options.and_span(span),
+        (None, Some(half_life), None, None) => options.and_half_life(half_life),
+        (None, None, Some(alpha), None) => EWMOptions { alpha, ..options },
+        (None, None, None, Some(com)) => options.and_com(com),
+        _ => {
+            return Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("Exactly one of span, half_life, alpha or com is expected"))))
+        }
+    };
+
+    if !(options.alpha > 0.0 && options.alpha <= 1.0) {
+        return Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("Smoothing factor must be in (0, 1]"))))
+    }
+
+    Ok(
+        options
+            .and_adjust(parameters.adjust.unwrap_or(true))
+            .and_min_periods(parameters.min_periods.unwrap_or(1))
+            .and_ignore_nulls(parameters.ignore_nulls.unwrap_or(false))
+    )
+}
+
+
+/// Appends the exponentially weighted mean of `columns`, computed per `by`
+/// group with rows ordered by `order_by`, as `{column}_{suffix}` columns
+/// (`ewm` being the default suffix).
+pub fn ewm(
+    lf: LazyFrame,
+    parameters: &EwmRawParameters
+) -> Result<LazyFrame, Box<dyn std::error::Error>> {
+    let options = ewm_options(parameters)?;
+    let by: Vec<String> = parameters.by.clone().unwrap_or_default();
+    let partition: Vec<Expr> = by.iter().map(col).collect();
+    let suffix = parameters.suffix.clone().unwrap_or("ewm".to_string());
+
+    let mut exprs: Vec<Expr> = Vec::with_capacity(parameters.columns.len());
+    for column in parameters.columns.iter() {
+        let mut expr = col(column).ewm_mean(options);
+        if !partition.is_empty() {
+            expr = expr.over(&partition);
+        }
+        exprs.push(expr.alias(format!("{column}_{suffix}")));
+    }
+
+    let lf = match &parameters.order_by {
+        Some(order_by) => {
+            let mut sort_columns: Vec<String> = by.clone();
+            sort_columns.push(order_by.clone());
+            lf.sort(sort_columns, SortMultipleOptions::default().with_maintain_order(true))
+        },
+        None => lf
+    };
+
+    Ok(lf.with_columns(exprs))
+}
@@ -0,0 +1,27 @@
+use polars::prelude::*;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map,Value};
+
+#[allow(unused)]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExplodeRawParameters {
+    pub columns: Vec<String>
+}
+
+impl ExplodeRawParameters {
+    pub fn from_map(map: &Map<String, Value>) -> Result<Self, serde_json::Error> {
+        let value = Value::Object(map.clone());
+        serde_json::from_value(value)
+    }
+}
+
+
+/// Explodes list `columns` into one row per element, repeating the other
+/// columns. Lists exploded together must have matching lengths.
+pub fn explode(
+    lf: LazyFrame,
+    parameters: &ExplodeRawParameters
+) -> Result<LazyFrame, Box<dyn std::error::Error>> {
+    Ok(lf.explode(parameters.columns.iter().map(col).collect::<Vec<Expr>>()))
+}
@@ -0,0 +1,105 @@
+use polars::{error::ErrString, prelude::*};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map,Value};
+
+#[allow(unused)]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FillNullRawParameters {
+    pub columns: Option<Vec<String>>,
+    pub strategy: String,
+    pub value: Option<Value>,
+    pub by: Option<Vec<String>>,
+    pub order_by: Option<String>
+}
+
+impl FillNullRawParameters {
+    pub fn from_map(map: &Map<String, Value>) -> Result<Self, serde_json::Error> {
+        let value = Value::Object(map.clone());
+        serde_json::from_value(value)
+    }
+}
+
+
+/// Converts a JSON scalar into a polars literal, keeping its natural type.
+pub fn literal_from_value(
+    value: &Value
+) -> Result<Expr, Box<dyn std::error::Error>> {
+    match value {
+        Value::Bool(value) => Ok(lit(*value)),
+        Value::String(value) => Ok(lit(value.clone())),
+        Value::Number(value) => {
+            if let Some(value) = value.as_i64() {
+                return Ok(lit(value))
+            }
+            if let Some(value) = value.as_u64() {
+                return Ok(lit(value))
+            }
+            Ok(lit(value.as_f64().unwrap()))
+        },
+        Value::Null => Ok(lit(NULL)),
+        _ => Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("Literal value must be a scalar"))))
+    }
+}
+
+
+/// Applies a null filling strategy to `expr`: null (no filling), forward,
+/// backward, constant, linear, mean, min, max or zero.
+pub fn fill_null_expr(
+    expr: Expr,
+    strategy: &str,
+    value: Option<&Value>
+) -> Result<Expr, Box<dyn std::error::Error>> {
+    match strategy {
+        "null" => Ok(expr),
+        "forward" => Ok(expr.forward_fill(None)),
+        "backward" => Ok(expr.backward_fill(None)),
+        "constant" => {
+            let value = match value {
+                Some(value) => value,
+                None => {
+                    return Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("value is expected for strategy 'constant'"))))
+                }
+            };
+            Ok(expr.fill_null(literal_from_value(value)?))
+        },
+        "linear" => Ok(expr.interpolate(InterpolationMethod::Linear)),
+        "mean" => Ok(expr.clone().fill_null(expr.mean())),
+        "min" => Ok(expr.clone().fill_null(expr.min())),
+        "max" => Ok(expr.clone().fill_null(expr.max())),
+        "zero" => Ok(expr.fill_null(lit(0))),
+        _ => Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("Invalid fill strategy"))))
+    }
+}
+
+
+/// Fills nulls of `columns` (all other columns when omitted) per `by` group,
+/// rows being ordered by `order_by` when given.
+pub fn fill_null(
+    lf: LazyFrame,
+    parameters: &FillNullRawParameters
+) -> Result<LazyFrame, Box<dyn std::error::Error>> {
+    let by: Vec<String> = parameters.by.clone().unwrap_or_default();
+
+    let mut keys: Vec<String> = by.clone();
+    if let Some(order_by) = parameters.order_by.clone() {
+        keys.push(order_by);
+    }
+
+    let target: Expr = match &parameters.columns {
+        Some(columns) => cols(columns),
+        None => all().exclude(&keys)
+    };
+
+    let mut expr = fill_null_expr(target, &parameters.strategy, parameters.value.as_ref())?;
+    if !by.is_empty() {
+        expr = expr.over(by.iter().map(col).collect::<Vec<Expr>>());
+    }
+
+    let lf = match parameters.order_by {
+        Some(_) => lf.sort(keys, SortMultipleOptions::default().with_maintain_order(true)),
+        None => lf
+    };
+
+    Ok(lf.with_columns([expr]))
+}
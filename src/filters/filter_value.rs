@@ -9,11 +9,31 @@ pub fn filter_value(
     value: &Value,
     r#type: &String
 ) -> Result<LazyFrame, Box<dyn std::error::Error>> {
+    let filter_expr: Expr = filter_value_expr(column, filter, value, r#type)?;
+
+    Ok(feed_left.filter(filter_expr))
+}
+
+
+/// Builds the predicate `column <filter> value` used by `filter_value`, the
+/// value being read as `type` (int, uint or float). A value that cannot be
+/// read as `type` is an error.
+pub fn filter_value_expr(
+    column: &String,
+    filter: &String,
+    value: &Value,
+    r#type: &String
+) -> Result<Expr, Box<dyn std::error::Error>> {
     let mut filter_expr: Expr = col(column);
 
     match r#type.as_str() {
         "int" => {
-            let value: i64 = value.as_i64().unwrap();
+            let value: i64 = match value.as_i64() {
+                Some(value) => value,
+                None => {
+                    return Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("value must be an integer for type int"))))
+                }
+            };
 
             match filter.as_str() {
                 "gte" => {
@@ -36,10 +56,15 @@ pub fn filter_value(
                 }
             }
 
-            return Ok(feed_left.filter(filter_expr))
+            return Ok(filter_expr)
         },
         "uint" => {
-            let value: u64 = value.as_u64().unwrap();
+            let value: u64 = match value.as_u64() {
+                Some(value) => value,
+                None => {
+                    return Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("value must be a positive integer for type uint"))))
+                }
+            };
 
             match filter.as_str() {
                 "gte" => {
@@ -62,10 +87,15 @@ pub fn filter_value(
                 }
             }
 
-            return Ok(feed_left.filter(filter_expr))
+            return Ok(filter_expr)
         },
         "float" => {
-            let value: f64 = value.as_f64().unwrap();
+            let value: f64 = match value.as_f64() {
+                Some(value) => value,
+                None => {
+                    return Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("value must be a number for type float"))))
+                }
+            };
 
             match filter.as_str() {
                 "gte" => {
@@ -88,7 +118,7 @@ pub fn filter_value(
                 }
             }
 
-            return Ok(feed_left.filter(filter_expr))
+            return Ok(filter_expr)
         },
         _ => {
             return Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("Invalid type"))))
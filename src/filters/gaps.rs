@@ -0,0 +1,73 @@
+use polars::{error::ErrString, prelude::*};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map,Value};
+
+use super::resample::physical_step;
+
+#[allow(unused)]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GapsRawParameters {
+    pub time_column: String,
+    pub threshold: Value,
+    pub by: Option<Vec<String>>,
+    pub time_unit: Option<String>
+}
+
+impl GapsRawParameters {
+    pub fn from_map(map: &Map<String, Value>) -> Result<Self, serde_json::Error> {
+        let value = Value::Object(map.clone());
+        serde_json::from_value(value)
+    }
+}
+
+
+/// Lists every interval between two consecutive timestamps of a `by` key
+/// that is larger than `threshold`, as `(key, gap_start, gap_end, duration)`.
+///
+/// On integer epoch columns, duration string thresholds such as `"15m"` are
+/// converted with `time_unit` (s, ms, us or ns).
+pub fn gaps(
+    mut lf: LazyFrame,
+    parameters: &GapsRawParameters
+) -> Result<LazyFrame, Box<dyn std::error::Error>> {
+    let time_column = parameters.time_column.as_str();
+    let by: Vec<String> = parameters.by.clone().unwrap_or_default();
+
+    let schema = lf.collect_schema()?;
+    let dtype: DataType = match schema.get(time_column) {
+        Some(dtype) => dtype.clone(),
+        None => {
+            return Err(Box::new(PolarsError::ColumnNotFound(ErrString::new_static("time_column not found in feed"))))
+        }
+    };
+    let threshold = physical_step(&parameters.threshold, &dtype, parameters.time_unit.as_deref())?;
+
+    let mut sort_columns: Vec<String> = by.clone();
+    sort_columns.push(time_column.to_string());
+
+    let partition: Vec<Expr> = by.iter().map(col).collect();
+    let mut gap_start: Expr = col(time_column).shift(lit(1));
+    if !partition.is_empty() {
+        gap_start = gap_start.over(&partition);
+    }
+
+    let physical_duration: Expr = col("gap_end").to_physical().cast(DataType::Int64)
+        - col("gap_start").to_physical().cast(DataType::Int64);
+
+    let mut selection: Vec<Expr> = partition.clone();
+    selection.push(col("gap_start"));
+    selection.push(col("gap_end"));
+    selection.push((col("gap_end") - col("gap_start")).alias("duration"));
+
+    Ok(
+        lf
+            .sort(sort_columns, SortMultipleOptions::default().with_maintain_order(true))
+            .with_columns([
+                gap_start.alias("gap_start"),
+                col(time_column).alias("gap_end")
+            ])
+            .filter(physical_duration.gt(lit(threshold)))
+            .select(selection)
+    )
+}
@@ -0,0 +1,89 @@
+use polars::{error::ErrString, prelude::*};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map,Value};
+
+#[allow(unused)]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JsonDecodeRawParameters {
+    pub column: String,
+    pub schema: Option<Map<String, Value>>,
+    pub infer_schema_length: Option<usize>,
+    pub alias: Option<String>
+}
+
+impl JsonDecodeRawParameters {
+    pub fn from_map(map: &Map<String, Value>) -> Result<Self, serde_json::Error> {
+        let value = Value::Object(map.clone());
+        serde_json::from_value(value)
+    }
+}
+
+
+/// Reads a data type from its JSON description: a type name such as
+/// `"int64"`, `"string"` or `"datetime[ms]"`, an object of fields for a
+/// struct, or a one element array for a list.
+pub fn parse_dtype(
+    dtype: &Value
+) -> Result<DataType, Box<dyn std::error::Error>> {
+    match dtype {
+        Value::Object(fields) => {
+            let mut struct_fields: Vec<Field> = Vec::with_capacity(fields.len());
+            for (name, field_dtype) in fields.iter() {
+                struct_fields.push(Field::new(name.into(), parse_dtype(field_dtype)?));
+            }
+            Ok(DataType::Struct(struct_fields))
+        },
+        Value::Array(inner) if inner.len() == 1 => {
+            Ok(DataType::List(Box::new(parse_dtype(&inner[0])?)))
+        },
+        Value::String(name) => match name.to_lowercase().as_str() {
+            "bool" | "boolean" => Ok(DataType::Boolean),
+            "int8" => Ok(DataType::Int8),
+            "int16" => Ok(DataType::Int16),
+            "int32" => Ok(DataType::Int32),
+            "int64" => Ok(DataType::Int64),
+            "uint8" => Ok(DataType::UInt8),
+            "uint16" => Ok(DataType::UInt16),
+            "uint32" => Ok(DataType::UInt32),
+            "uint64" => Ok(DataType::UInt64),
+            "float32" => Ok(DataType::Float32),
+            "float64" => Ok(DataType::Float64),
+            "str" | "string" | "utf8" => Ok(DataType::String),
+            "date" => Ok(DataType::Date),
+            "datetime" | "datetime[us]" => Ok(DataType::Datetime(TimeUnit::Microseconds, None)),
+            "datetime[ms]" => Ok(DataType::Datetime(TimeUnit::Milliseconds, None)),
+            "datetime[ns]" => Ok(DataType::Datetime(TimeUnit::Nanoseconds, None)),
+            _ => Err(Box::new(PolarsError::InvalidOperation(format!("Unknown data type {name}").into())))
+        },
+        _ => Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("A data type must be a name, an object of fields or a one element array"))))
+    }
+}
+
+
+/// Parses the JSON strings of `column` into a struct column, following
+/// `schema` when given or else inferring it from the first
+/// `infer_schema_length` rows. Null strings stay null, invalid JSON fails
+/// the step.
+pub fn json_decode(
+    lf: LazyFrame,
+    parameters: &JsonDecodeRawParameters
+) -> Result<LazyFrame, Box<dyn std::error::Error>> {
+    let column = parameters.column.as_str();
+    let alias = parameters.alias.clone().unwrap_or(column.to_string());
+
+    let dtype: Option<DataType> = match &parameters.schema {
+        Some(schema) => Some(parse_dtype(&Value::Object(schema.clone()))?),
+        None => None
+    };
+    let infer_schema_length = parameters.infer_schema_length.or(Some(100));
+
+    Ok(
+        lf.with_columns([
+            col(column)
+                .str()
+                .json_decode(dtype, infer_schema_length)
+                .alias(alias)
+        ])
+    )
+}
@@ -0,0 +1,154 @@
+use polars::{error::ErrString, prelude::*};
+use sha2::{Digest, Sha256};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map,Value};
+
+const DEFAULT_SALT_ENV: &str = "MASK_SALT";
+
+#[allow(unused)]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MaskRawParameters {
+    pub columns: Vec<String>,
+    pub method: String,
+    pub salt_env: Option<String>,
+    pub length: Option<usize>,
+    pub pattern: Option<String>,
+    pub replacement: Option<String>,
+    pub every: Option<Value>
+}
+
+impl MaskRawParameters {
+    pub fn from_map(map: &Map<String, Value>) -> Result<Self, serde_json::Error> {
+        let value = Value::Object(map.clone());
+        serde_json::from_value(value)
+    }
+}
+
+
+/// Hex encoded SHA-256 of every value prefixed by `salt`, nulls are kept.
+fn hash_expr(
+    expr: Expr,
+    salt: String
+) -> Expr {
+    expr
+        .cast(DataType::String)
+        .map(
+            move |column| {
+                let hashed: StringChunked = column
+                    .str()?
+                    .apply_values(|value| {
+                        let mut hasher = Sha256::new();
+                        hasher.update(salt.as_bytes());
+                        hasher.update(value.as_bytes());
+                        format!("{:x}", hasher.finalize()).into()
+                    });
+                Ok(Some(hashed.into_column()))
+            },
+            GetOutput::from_type(DataType::String)
+        )
+}
+
+
+/// Generalizes a column to a coarser value: temporal columns are truncated
+/// to the `every` duration (e.g. `"1h"`), numeric ones bucketed down to a
+/// multiple of `every`.
+fn generalize_expr(
+    column: &str,
+    dtype: &DataType,
+    every: &Value
+) -> Result<Expr, Box<dyn std::error::Error>> {
+    match dtype {
+        DataType::Datetime(_, _) | DataType::Date => match every.as_str() {
+            Some(every) => Ok(col(column).dt().truncate(lit(every.to_string()))),
+            None => Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("every must be a duration string for temporal columns"))))
+        },
+        dtype if dtype.is_integer() => match every.as_i64() {
+            Some(width) if width > 0 => Ok(col(column).floor_div(lit(width)) * lit(width)),
+            _ => Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("every must be a positive integer for integer columns"))))
+        },
+        dtype if dtype.is_float() => match every.as_f64() {
+            Some(width) if width > 0.0 => Ok(col(column).floor_div(lit(width)) * lit(width)),
+            _ => Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("every must be a positive number for float columns"))))
+        },
+        _ => Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("generalize expects a temporal or numeric column"))))
+    }
+}
+
+
+/// Masks `columns` in place so that raw identifiers never leave the feed.
+///
+/// - `hash`: salted SHA-256, the salt being read from the environment
+///   variable named by `salt_env` (`MASK_SALT` by default).
+/// - `truncate`: keeps the first `length` characters.
+/// - `redact`: replaces every match of the `pattern` regex (the whole value
+///   by default) with `replacement` (`"***"` by default).
+/// - `generalize`: rounds timestamps down to `every` or buckets numbers.
+pub fn mask(
+    mut lf: LazyFrame,
+    parameters: &MaskRawParameters
+) -> Result<LazyFrame, Box<dyn std::error::Error>> {
+    let schema = lf.collect_schema()?;
+    for column in parameters.columns.iter() {
+        schema.try_get(column)?;
+    }
+
+    let exprs: Vec<Expr> = match parameters.method.as_str() {
+        "hash" => {
+            let salt_env = parameters.salt_env.clone().unwrap_or(DEFAULT_SALT_ENV.to_string());
+            let salt = match std::env::var(&salt_env) {
+                Ok(salt) if !salt.is_empty() => salt,
+                _ => {
+                    return Err(Box::new(PolarsError::InvalidOperation(format!("Environment variable {salt_env} must hold the hashing salt").into())))
+                }
+            };
+            parameters.columns.iter().map(|column| hash_expr(col(column), salt.clone()).alias(column)).collect()
+        },
+        "truncate" => {
+            let length = match parameters.length {
+                Some(length) => length as u64,
+                None => {
+                    return Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("length is expected for truncate"))))
+                }
+            };
+            parameters
+                .columns
+                .iter()
+                .map(|column| col(column).cast(DataType::String).str().slice(lit(0), lit(length)).alias(column))
+                .collect()
+        },
+        "redact" => {
+            let pattern = parameters.pattern.clone().unwrap_or("^.*$".to_string());
+            let replacement = parameters.replacement.clone().unwrap_or("***".to_string());
+            parameters
+                .columns
+                .iter()
+                .map(|column| {
+                    col(column)
+                        .cast(DataType::String)
+                        .str()
+                        .replace_all(lit(pattern.clone()), lit(replacement.clone()), false)
+                        .alias(column)
+                })
+                .collect()
+        },
+        "generalize" => {
+            let every = match &parameters.every {
+                Some(every) => every,
+                None => {
+                    return Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("every is expected for generalize"))))
+                }
+            };
+            let mut exprs: Vec<Expr> = Vec::with_capacity(parameters.columns.len());
+            for column in parameters.columns.iter() {
+                exprs.push(generalize_expr(column, schema.try_get(column)?, every)?.alias(column));
+            }
+            exprs
+        },
+        _ => {
+            return Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("Invalid method, expected hash, truncate, redact or generalize"))))
+        }
+    };
+
+    Ok(lf.with_columns(exprs))
+}
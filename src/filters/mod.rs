@@ -1,10 +1,53 @@
 pub(crate) mod filter_value;
 pub(crate) mod correlation;
 pub(crate) mod duplicate_values;
+pub(crate) mod window;
+pub(crate) mod fill_null;
+pub(crate) mod resample;
+pub(crate) mod gaps;
+pub(crate) mod staleness;
+pub(crate) mod aggregation;
+pub(crate) mod sessionize;
+pub(crate) mod ohlc;
+pub(crate) mod ewm;
+pub(crate) mod time_weighted_mean;
+pub(crate) mod regression;
+pub(crate) mod scale;
+pub(crate) mod one_hot;
+pub(crate) mod categorize;
+pub(crate) mod sample;
+pub(crate) mod split;
+pub(crate) mod explode;
+pub(crate) mod unnest;
+pub(crate) mod json_decode;
+pub(crate) mod temporal;
+pub(crate) mod mask;
+pub(crate) mod case;
 
 use correlation::correlate_columns;
 use filter_value::filter_value;
 use duplicate_values::find_duplicate_values_in_column;
+use window::{window,WindowRawParameters};
+use fill_null::{fill_null,FillNullRawParameters};
+use resample::{resample,ResampleRawParameters};
+use gaps::{gaps,GapsRawParameters};
+use staleness::{staleness,StalenessRawParameters};
+use sessionize::{sessionize,SessionizeRawParameters};
+use ohlc::{ohlc,OhlcRawParameters};
+use ewm::{ewm,EwmRawParameters};
+use time_weighted_mean::{time_weighted_mean,TimeWeightedMeanRawParameters};
+use regression::{regression,RegressionRawParameters};
+use scale::{scale,ScaleRawParameters};
+use one_hot::{one_hot,OneHotRawParameters};
+use categorize::{categorize,CategorizeRawParameters};
+use sample::{sample,SampleRawParameters};
+use split::{split,SplitRawParameters};
+use explode::{explode,ExplodeRawParameters};
+use unnest::{unnest,UnnestRawParameters};
+use json_decode::{json_decode,JsonDecodeRawParameters};
+use temporal::{temporal,TemporalRawParameters};
+use mask::{mask,MaskRawParameters};
+use case::{case,CaseRawParameters};
 
 use polars::prelude::*;
 use polars::error::ErrString;
@@ -85,7 +128,238 @@ pub fn get_feed_from_filters(
                     .as_str()
                     .unwrap();
 
-                filtered_lf = find_duplicate_values_in_column(filtered_lf, &column);
+                filtered_lf = find_duplicate_values_in_column(filtered_lf, column);
+            },
+            "window" => {
+                let parameters = WindowRawParameters::from_map(
+                    filter_obj
+                        .get("parameters")
+                        .expect("parameters is expected for operation 'window'")
+                        .as_object()
+                        .expect("parameters must be an object")
+                )?;
+
+                filtered_lf = window(filtered_lf, &parameters)?;
+            },
+            "resample" => {
+                let parameters = ResampleRawParameters::from_map(
+                    filter_obj
+                        .get("parameters")
+                        .expect("parameters is expected for operation 'resample'")
+                        .as_object()
+                        .expect("parameters must be an object")
+                )?;
+
+                filtered_lf = resample(filtered_lf, &parameters)?;
+            },
+            "fill_null" => {
+                let parameters = FillNullRawParameters::from_map(
+                    filter_obj
+                        .get("parameters")
+                        .expect("parameters is expected for operation 'fill_null'")
+                        .as_object()
+                        .expect("parameters must be an object")
+                )?;
+
+                filtered_lf = fill_null(filtered_lf, &parameters)?;
+            },
+            "gaps" => {
+                let parameters = GapsRawParameters::from_map(
+                    filter_obj
+                        .get("parameters")
+                        .expect("parameters is expected for operation 'gaps'")
+                        .as_object()
+                        .expect("parameters must be an object")
+                )?;
+
+                filtered_lf = gaps(filtered_lf, &parameters)?;
+            },
+            "staleness" => {
+                let parameters = StalenessRawParameters::from_map(
+                    filter_obj
+                        .get("parameters")
+                        .expect("parameters is expected for operation 'staleness'")
+                        .as_object()
+                        .expect("parameters must be an object")
+                )?;
+
+                filtered_lf = staleness(filtered_lf, &parameters)?;
+            },
+            "sessionize" => {
+                let parameters = SessionizeRawParameters::from_map(
+                    filter_obj
+                        .get("parameters")
+                        .expect("parameters is expected for operation 'sessionize'")
+                        .as_object()
+                        .expect("parameters must be an object")
+                )?;
+
+                filtered_lf = sessionize(filtered_lf, &parameters)?;
+            },
+            "ohlc" => {
+                let parameters = OhlcRawParameters::from_map(
+                    filter_obj
+                        .get("parameters")
+                        .expect("parameters is expected for operation 'ohlc'")
+                        .as_object()
+                        .expect("parameters must be an object")
+                )?;
+
+                filtered_lf = ohlc(filtered_lf, &parameters)?;
+            },
+            "ewm" => {
+                let parameters = EwmRawParameters::from_map(
+                    filter_obj
+                        .get("parameters")
+                        .expect("parameters is expected for operation 'ewm'")
+                        .as_object()
+                        .expect("parameters must be an object")
+                )?;
+
+                filtered_lf = ewm(filtered_lf, &parameters)?;
+            },
+            "time_weighted_mean" => {
+                let parameters = TimeWeightedMeanRawParameters::from_map(
+                    filter_obj
+                        .get("parameters")
+                        .expect("parameters is expected for operation 'time_weighted_mean'")
+                        .as_object()
+                        .expect("parameters must be an object")
+                )?;
+
+                filtered_lf = time_weighted_mean(filtered_lf, &parameters)?;
+            },
+            "regression" => {
+                let parameters = RegressionRawParameters::from_map(
+                    filter_obj
+                        .get("parameters")
+                        .expect("parameters is expected for operation 'regression'")
+                        .as_object()
+                        .expect("parameters must be an object")
+                )?;
+
+                filtered_lf = regression(filtered_lf, &parameters)?;
+            },
+            "scale" => {
+                let parameters = ScaleRawParameters::from_map(
+                    filter_obj
+                        .get("parameters")
+                        .expect("parameters is expected for operation 'scale'")
+                        .as_object()
+                        .expect("parameters must be an object")
+                )?;
+
+                filtered_lf = scale(filtered_lf, &parameters)?;
+            },
+            "one_hot" => {
+                let parameters = OneHotRawParameters::from_map(
+                    filter_obj
+                        .get("parameters")
+                        .expect("parameters is expected for operation 'one_hot'")
+                        .as_object()
+                        .expect("parameters must be an object")
+                )?;
+
+                filtered_lf = one_hot(filtered_lf, &parameters)?;
+            },
+            "categorize" => {
+                let parameters = CategorizeRawParameters::from_map(
+                    filter_obj
+                        .get("parameters")
+                        .expect("parameters is expected for operation 'categorize'")
+                        .as_object()
+                        .expect("parameters must be an object")
+                )?;
+
+                filtered_lf = categorize(filtered_lf, &parameters);
+            },
+            "sample" => {
+                let parameters = SampleRawParameters::from_map(
+                    filter_obj
+                        .get("parameters")
+                        .expect("parameters is expected for operation 'sample'")
+                        .as_object()
+                        .expect("parameters must be an object")
+                )?;
+
+                filtered_lf = sample(filtered_lf, &parameters)?;
+            },
+            "split" => {
+                let parameters = SplitRawParameters::from_map(
+                    filter_obj
+                        .get("parameters")
+                        .expect("parameters is expected for operation 'split'")
+                        .as_object()
+                        .expect("parameters must be an object")
+                )?;
+
+                filtered_lf = split(filtered_lf, &parameters)?;
+            },
+            "explode" => {
+                let parameters = ExplodeRawParameters::from_map(
+                    filter_obj
+                        .get("parameters")
+                        .expect("parameters is expected for operation 'explode'")
+                        .as_object()
+                        .expect("parameters must be an object")
+                )?;
+
+                filtered_lf = explode(filtered_lf, &parameters)?;
+            },
+            "unnest" => {
+                let parameters = UnnestRawParameters::from_map(
+                    filter_obj
+                        .get("parameters")
+                        .expect("parameters is expected for operation 'unnest'")
+                        .as_object()
+                        .expect("parameters must be an object")
+                )?;
+
+                filtered_lf = unnest(filtered_lf, &parameters)?;
+            },
+            "json_decode" => {
+                let parameters = JsonDecodeRawParameters::from_map(
+                    filter_obj
+                        .get("parameters")
+                        .expect("parameters is expected for operation 'json_decode'")
+                        .as_object()
+                        .expect("parameters must be an object")
+                )?;
+
+                filtered_lf = json_decode(filtered_lf, &parameters)?;
+            },
+            "temporal" => {
+                let parameters = TemporalRawParameters::from_map(
+                    filter_obj
+                        .get("parameters")
+                        .expect("parameters is expected for operation 'temporal'")
+                        .as_object()
+                        .expect("parameters must be an object")
+                )?;
+
+                filtered_lf = temporal(filtered_lf, &parameters)?;
+            },
+            "mask" => {
+                let parameters = MaskRawParameters::from_map(
+                    filter_obj
+                        .get("parameters")
+                        .expect("parameters is expected for operation 'mask'")
+                        .as_object()
+                        .expect("parameters must be an object")
+                )?;
+
+                filtered_lf = mask(filtered_lf, &parameters)?;
+            },
+            "case" => {
+                let parameters = CaseRawParameters::from_map(
+                    filter_obj
+                        .get("parameters")
+                        .expect("parameters is expected for operation 'case'")
+                        .as_object()
+                        .expect("parameters must be an object")
+                )?;
+
+                filtered_lf = case(filtered_lf, &parameters)?;
             },
             _ => {
                 return Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("Operation is unknown"))))
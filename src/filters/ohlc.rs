@@ -0,0 +1,186 @@
+use polars::{error::ErrString, prelude::*};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map,Value};
+
+use super::aggregation::{aggregation_expr,AggregationRawParameters};
+use super::resample::physical_step;
+
+#[allow(unused)]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OhlcRawParameters {
+    pub time_column: String,
+    pub column: String,
+    pub every: Value,
+    pub period: Option<Value>,
+    pub offset: Option<Value>,
+    pub by: Option<Vec<String>>,
+    pub weight_column: Option<String>,
+    pub label: Option<String>,
+    pub closed: Option<String>,
+    pub start_by: Option<String>,
+    pub time_unit: Option<String>,
+    pub aggregations: Option<Vec<AggregationRawParameters>>
+}
+
+impl OhlcRawParameters {
+    pub fn from_map(map: &Map<String, Value>) -> Result<Self, serde_json::Error> {
+        let value = Value::Object(map.clone());
+        serde_json::from_value(value)
+    }
+}
+
+
+/// Parses a bucket size for a time column of type `dtype`: integers are index
+/// units (raw epoch timestamps), strings are polars durations such as `"5m"`
+/// or `"1h30m"`.
+///
+/// On integer epoch columns, duration strings are converted to index units
+/// with `time_unit`, and rejected without it.
+pub fn bucket_duration(
+    value: &Value,
+    dtype: &DataType,
+    time_unit: Option<&str>
+) -> Result<Duration, Box<dyn std::error::Error>> {
+    if let Some(value) = value.as_i64() {
+        return Ok(Duration::try_parse(format!("{value}i").as_str())?)
+    }
+
+    match value.as_str() {
+        Some(_) if dtype.is_integer() => {
+            let step = physical_step(value, dtype, time_unit)?;
+            Ok(Duration::try_parse(format!("{step}i").as_str())?)
+        },
+        Some(value) => Ok(Duration::try_parse(value)?),
+        None => Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("Bucket size must be an integer or a duration string"))))
+    }
+}
+
+
+/// Builds the dynamic group-by options shared by time-bucket rollups, for a
+/// time column of type `dtype`.
+#[allow(clippy::too_many_arguments)]
+pub fn bucket_options(
+    dtype: &DataType,
+    time_unit: Option<&str>,
+    every: &Value,
+    period: Option<&Value>,
+    offset: Option<&Value>,
+    label: Option<&String>,
+    closed: Option<&String>,
+    start_by: Option<&String>
+) -> Result<DynamicGroupOptions, Box<dyn std::error::Error>> {
+    let every = bucket_duration(every, dtype, time_unit)?;
+    let period = match period {
+        Some(period) => bucket_duration(period, dtype, time_unit)?,
+        None => every
+    };
+    let offset = match offset {
+        Some(offset) => bucket_duration(offset, dtype, time_unit)?,
+        None => Duration::parse("0ns")
+    };
+
+    let label = match label.map(|l| l.as_str()) {
+        None | Some("left") => Label::Left,
+        Some("right") => Label::Right,
+        Some("datapoint") => Label::DataPoint,
+        _ => {
+            return Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("Invalid label"))))
+        }
+    };
+
+    let closed_window = match closed.map(|c| c.as_str()) {
+        None | Some("left") => ClosedWindow::Left,
+        Some("right") => ClosedWindow::Right,
+        Some("both") => ClosedWindow::Both,
+        Some("none") => ClosedWindow::None,
+        _ => {
+            return Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("Invalid closed window"))))
+        }
+    };
+
+    let start_by = match start_by.map(|s| s.as_str()) {
+        None | Some("window") => StartBy::WindowBound,
+        Some("datapoint") => StartBy::DataPoint,
+        _ => {
+            return Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("Invalid start_by"))))
+        }
+    };
+
+    Ok(DynamicGroupOptions {
+        every,
+        period,
+        offset,
+        label,
+        include_boundaries: false,
+        closed_window,
+        start_by,
+        ..Default::default()
+    })
+}
+
+
+/// Produces open/high/low/close/count bars of `column` per time bucket and
+/// `by` key, plus a volume-weighted average when `weight_column` is given.
+///
+/// On integer epoch time columns, duration strings need the `time_unit` of
+/// the epoch (s, ms, us or ns).
+pub fn ohlc(
+    mut lf: LazyFrame,
+    parameters: &OhlcRawParameters
+) -> Result<LazyFrame, Box<dyn std::error::Error>> {
+    let time_column = parameters.time_column.as_str();
+    let column = parameters.column.as_str();
+    let by: Vec<String> = parameters.by.clone().unwrap_or_default();
+
+    let schema = lf.collect_schema()?;
+    let dtype: DataType = match schema.get(time_column) {
+        Some(dtype) => dtype.clone(),
+        None => {
+            return Err(Box::new(PolarsError::ColumnNotFound(ErrString::new_static("time_column not found in feed"))))
+        }
+    };
+
+    let options = bucket_options(
+        &dtype,
+        parameters.time_unit.as_deref(),
+        &parameters.every,
+        parameters.period.as_ref(),
+        parameters.offset.as_ref(),
+        parameters.label.as_ref(),
+        parameters.closed.as_ref(),
+        parameters.start_by.as_ref()
+    )?;
+
+    let mut aggs: Vec<Expr> = vec![
+        col(column).first().alias("open"),
+        col(column).max().alias("high"),
+        col(column).min().alias("low"),
+        col(column).last().alias("close"),
+        len().alias("count")
+    ];
+    if let Some(weight_column) = &parameters.weight_column {
+        aggs.push(
+            ((col(column) * col(weight_column)).sum() / col(weight_column).sum())
+                .alias("vwap")
+        );
+    }
+    for aggregation in parameters.aggregations.clone().unwrap_or_default().iter() {
+        aggs.push(aggregation_expr(aggregation)?);
+    }
+
+    let mut sort_columns: Vec<String> = by.clone();
+    sort_columns.push(time_column.to_string());
+
+    Ok(
+        lf
+            .sort(sort_columns.clone(), SortMultipleOptions::default().with_maintain_order(true))
+            .group_by_dynamic(
+                col(time_column),
+                by.iter().map(col).collect::<Vec<Expr>>(),
+                options
+            )
+            .agg(aggs)
+            .sort(sort_columns, SortMultipleOptions::default())
+    )
+}
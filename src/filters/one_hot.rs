@@ -0,0 +1,80 @@
+use polars::prelude::*;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map,Value};
+
+#[allow(unused)]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OneHotRawParameters {
+    pub columns: Vec<String>,
+    pub categories: Option<Vec<String>>,
+    pub drop_original: Option<bool>
+}
+
+impl OneHotRawParameters {
+    pub fn from_map(map: &Map<String, Value>) -> Result<Self, serde_json::Error> {
+        let value = Value::Object(map.clone());
+        serde_json::from_value(value)
+    }
+}
+
+
+/// Categories to encode for `column`: the given list, or the categories of
+/// an `Enum` column. They are never discovered from the data, which would
+/// execute the feed while building it.
+fn column_categories(
+    column: &str,
+    dtype: &DataType,
+    categories: Option<&Vec<String>>
+) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    if let Some(categories) = categories {
+        return Ok(categories.clone())
+    }
+
+    if let DataType::Enum(Some(rev_map), _) = dtype {
+        return Ok(
+            rev_map
+                .get_categories()
+                .values_iter()
+                .map(|category| category.to_string())
+                .collect()
+        )
+    }
+
+    Err(format!("categories are expected to one-hot encode {column}, unless it is an Enum (see categorize)").into())
+}
+
+
+/// Appends a `{column}_{category}` indicator column (0/1) per category of
+/// each of `columns`, dropping the encoded columns unless told otherwise.
+pub fn one_hot(
+    mut lf: LazyFrame,
+    parameters: &OneHotRawParameters
+) -> Result<LazyFrame, Box<dyn std::error::Error>> {
+    let schema = lf.collect_schema()?;
+
+    let mut exprs: Vec<Expr> = Vec::new();
+    for column in parameters.columns.iter() {
+        let dtype = schema.try_get(column)?;
+        let categories = column_categories(column, dtype, parameters.categories.as_ref())?;
+
+        for category in categories.iter() {
+            exprs.push(
+                col(column)
+                    .cast(DataType::String)
+                    .eq(lit(category.clone()))
+                    .fill_null(lit(false))
+                    .cast(DataType::UInt8)
+                    .alias(format!("{column}_{category}"))
+            );
+        }
+    }
+
+    let encoded = lf.with_columns(exprs);
+
+    if parameters.drop_original.unwrap_or(true) {
+        return Ok(encoded.drop(parameters.columns.clone()))
+    }
+
+    Ok(encoded)
+}
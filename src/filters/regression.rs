@@ -0,0 +1,279 @@
+use polars::{error::ErrString, prelude::*};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map,Value};
+
+#[allow(unused)]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RegressionRawParameters {
+    pub target: String,
+    pub features: Vec<String>,
+    pub by: Option<Vec<String>>,
+    pub append_fitted: Option<bool>
+}
+
+impl RegressionRawParameters {
+    pub fn from_map(map: &Map<String, Value>) -> Result<Self, serde_json::Error> {
+        let value = Value::Object(map.clone());
+        serde_json::from_value(value)
+    }
+}
+
+
+/// Solves `a * x = b` by Gaussian elimination with partial pivoting.
+/// Returns `None` when the system is singular.
+#[allow(clippy::needless_range_loop)]
+fn solve(
+    mut a: Vec<Vec<f64>>,
+    mut b: Vec<f64>
+) -> Option<Vec<f64>> {
+    let size = b.len();
+
+    for pivot in 0..size {
+        let best = (pivot..size).max_by(|&i, &j| a[i][pivot].abs().total_cmp(&a[j][pivot].abs()))?;
+        if a[best][pivot].abs() < 1e-12 {
+            return None
+        }
+        a.swap(pivot, best);
+        b.swap(pivot, best);
+
+        for row in (pivot + 1)..size {
+            let factor = a[row][pivot] / a[pivot][pivot];
+            for column in pivot..size {
+                a[row][column] -= factor * a[pivot][column];
+            }
+            b[row] -= factor * b[pivot];
+        }
+    }
+
+    let mut x = vec![0.0; size];
+    for row in (0..size).rev() {
+        let mut value = b[row];
+        for column in (row + 1)..size {
+            value -= a[row][column] * x[column];
+        }
+        x[row] = value / a[row][row];
+    }
+
+    Some(x)
+}
+
+
+fn stat(
+    stats: &DataFrame,
+    name: &str,
+    row: usize
+) -> PolarsResult<f64> {
+    let column = stats.column(name)?.cast(&DataType::Float64)?;
+    Ok(column.f64()?.get(row).unwrap_or(f64::NAN))
+}
+
+
+/// Solves the normal equations of every group of `stats`, the per-group
+/// sufficient statistics computed by `regression`, into its summary rows.
+#[allow(clippy::needless_range_loop)]
+fn fit(
+    stats: DataFrame,
+    by: &[String],
+    features: &[String]
+) -> PolarsResult<DataFrame> {
+    let size = features.len();
+    let groups = stats.height();
+
+    let mut intercepts: Vec<f64> = Vec::with_capacity(groups);
+    let mut coefficients: Vec<Vec<f64>> = vec![Vec::with_capacity(groups); size];
+    let mut r_squared: Vec<f64> = Vec::with_capacity(groups);
+    let mut residual_std_errors: Vec<f64> = Vec::with_capacity(groups);
+
+    for row in 0..groups {
+        let n = stat(&stats, "n", row)?;
+        let var_y = stat(&stats, "var_y", row)?;
+
+        let mut cov_xx: Vec<Vec<f64>> = vec![vec![0.0; size]; size];
+        let mut cov_xy: Vec<f64> = vec![0.0; size];
+        for i in 0..size {
+            cov_xy[i] = stat(&stats, &format!("cov_{i}_y"), row)?;
+            for j in i..size {
+                let value = stat(&stats, &format!("cov_{i}_{j}"), row)?;
+                cov_xx[i][j] = value;
+                cov_xx[j][i] = value;
+            }
+        }
+
+        let beta = solve(cov_xx, cov_xy.clone()).unwrap_or(vec![f64::NAN; size]);
+
+        let mut intercept = stat(&stats, "mean_y", row)?;
+        let mut explained = 0.0;
+        for i in 0..size {
+            intercept -= beta[i] * stat(&stats, &format!("mean_{i}"), row)?;
+            explained += beta[i] * cov_xy[i];
+            coefficients[i].push(beta[i]);
+        }
+
+        let sse = (n - 1.0) * (var_y - explained);
+        let degrees_of_freedom = n - size as f64 - 1.0;
+
+        intercepts.push(intercept);
+        r_squared.push(explained / var_y);
+        residual_std_errors.push(
+            if degrees_of_freedom > 0.0 { (sse.max(0.0) / degrees_of_freedom).sqrt() } else { f64::NAN }
+        );
+    }
+
+    let mut columns: Vec<Column> = Vec::with_capacity(by.len() + size + 4);
+    for key in by.iter() {
+        columns.push(stats.column(key)?.clone());
+    }
+    columns.push(Column::new("intercept".into(), intercepts));
+    for (i, feature) in features.iter().enumerate() {
+        columns.push(Column::new(format!("coef_{feature}").into(), coefficients[i].clone()));
+    }
+    columns.push(Column::new("r_squared".into(), r_squared));
+    columns.push(Column::new("residual_std_error".into(), residual_std_errors));
+    columns.push(stats.column("n")?.clone());
+
+    DataFrame::new(columns)
+}
+
+
+/// Fits an ordinary least squares regression of `target` on `features`, per
+/// `by` group, and returns one row per group with the intercept, a
+/// `coef_{feature}` column per feature, `r_squared`, `residual_std_error`
+/// and `n`.
+///
+/// The per-group sufficient statistics are aggregated lazily and the normal
+/// equations are solved when the feed is collected, so building the feed
+/// does not execute it. With `append_fitted`, the feed rows are returned
+/// instead, with `fitted` and `residual` columns appended.
+pub fn regression(
+    mut lf: LazyFrame,
+    parameters: &RegressionRawParameters
+) -> Result<LazyFrame, Box<dyn std::error::Error>> {
+    let target = parameters.target.as_str();
+    let features = parameters.features.clone();
+    let by: Vec<String> = parameters.by.clone().unwrap_or_default();
+
+    if features.is_empty() {
+        return Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("At least one feature is expected"))))
+    }
+
+    let mut complete: Expr = col(target).is_not_null();
+    for feature in features.iter() {
+        complete = complete.and(col(feature).is_not_null());
+    }
+
+    let mut aggs: Vec<Expr> = vec![
+        len().alias("n"),
+        col(target).cast(DataType::Float64).mean().alias("mean_y"),
+        col(target).cast(DataType::Float64).var(1).alias("var_y")
+    ];
+    for (i, feature) in features.iter().enumerate() {
+        aggs.push(col(feature).cast(DataType::Float64).mean().alias(format!("mean_{i}")));
+        aggs.push(cov(col(feature), col(target), 1).alias(format!("cov_{i}_y")));
+        for (j, feature_2) in features.iter().enumerate().skip(i) {
+            aggs.push(cov(col(feature), col(feature_2), 1).alias(format!("cov_{i}_{j}")));
+        }
+    }
+
+    let input_schema = lf.collect_schema()?;
+    let mut summary_schema = Schema::with_capacity(by.len() + features.len() + 4);
+    for key in by.iter() {
+        summary_schema.insert(key.into(), input_schema.try_get(key)?.clone());
+    }
+    summary_schema.insert("intercept".into(), DataType::Float64);
+    for feature in features.iter() {
+        summary_schema.insert(format!("coef_{feature}").into(), DataType::Float64);
+    }
+    summary_schema.insert("r_squared".into(), DataType::Float64);
+    summary_schema.insert("residual_std_error".into(), DataType::Float64);
+    summary_schema.insert("n".into(), IDX_DTYPE);
+    let summary_schema: SchemaRef = Arc::new(summary_schema);
+
+    let complete_lf = lf.clone().filter(complete);
+    let stats: LazyFrame = if by.is_empty() {
+        complete_lf.select(aggs)
+    } else {
+        complete_lf
+            .group_by(by.iter().map(col).collect::<Vec<Expr>>())
+            .agg(aggs)
+            .sort(by.clone(), SortMultipleOptions::default())
+    };
+
+    let fit_by = by.clone();
+    let fit_features = features.clone();
+    let summary: LazyFrame = stats.map(
+        move |stats| fit(stats, &fit_by, &fit_features),
+        AllowedOptimizations::empty(),
+        Some(Arc::new(move |_: &Schema| Ok(summary_schema.clone()))),
+        Some("regression")
+    );
+
+    if !parameters.append_fitted.unwrap_or(false) {
+        return Ok(summary)
+    }
+
+    let mut fitted: Expr = col("intercept");
+    let mut model_columns: Vec<String> = vec!["intercept".to_string()];
+    for feature in features.iter() {
+        fitted = fitted + col(format!("coef_{feature}").as_str()) * col(feature).cast(DataType::Float64);
+        model_columns.push(format!("coef_{feature}"));
+    }
+
+    let with_model: LazyFrame = if by.is_empty() {
+        lf.cross_join(summary.select([cols(&model_columns)]), None)
+    } else {
+        let keys: Vec<Expr> = by.iter().map(col).collect();
+        let mut selection = by.clone();
+        selection.extend(model_columns.clone());
+        lf.join(
+            summary.select([cols(selection)]),
+            &keys,
+            &keys,
+            JoinArgs::new(JoinType::Left)
+        )
+    };
+
+    Ok(
+        with_model
+            .with_columns([fitted.alias("fitted")])
+            .with_columns([(col(target).cast(DataType::Float64) - col("fitted")).alias("residual")])
+            .drop(model_columns)
+    )
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: &[f64], expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len());
+        for (actual, expected) in actual.iter().zip(expected.iter()) {
+            assert!((actual - expected).abs() < 1e-9, "{actual} != {expected}");
+        }
+    }
+
+    #[test]
+    fn solve_linear_system() {
+        let x = solve(vec![vec![2.0, 1.0], vec![1.0, 3.0]], vec![3.0, 5.0]).unwrap();
+        assert_close(&x, &[0.8, 1.4]);
+
+        let x = solve(
+            vec![vec![1.0, 1.0, 1.0], vec![0.0, 2.0, 5.0], vec![2.0, 5.0, -1.0]],
+            vec![6.0, -4.0, 27.0]
+        ).unwrap();
+        assert_close(&x, &[5.0, 3.0, -2.0]);
+    }
+
+    #[test]
+    fn solve_pivots_on_zero_diagonal() {
+        let x = solve(vec![vec![0.0, 1.0], vec![1.0, 0.0]], vec![2.0, 3.0]).unwrap();
+        assert_close(&x, &[3.0, 2.0]);
+    }
+
+    #[test]
+    fn solve_singular_system() {
+        assert!(solve(vec![vec![1.0, 2.0], vec![2.0, 4.0]], vec![3.0, 6.0]).is_none());
+        assert!(solve(vec![vec![0.0, 0.0], vec![0.0, 0.0]], vec![0.0, 0.0]).is_none());
+    }
+}
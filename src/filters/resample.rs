@@ -0,0 +1,217 @@
+use polars::{error::ErrString, prelude::*};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map,Value};
+
+use super::fill_null::fill_null_expr;
+
+#[allow(unused)]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ResampleRawParameters {
+    pub time_column: String,
+    pub every: Value,
+    pub by: Option<Vec<String>>,
+    pub strategy: Option<String>,
+    pub value: Option<Value>,
+    pub columns: Option<Vec<String>>,
+    pub time_unit: Option<String>
+}
+
+impl ResampleRawParameters {
+    pub fn from_map(map: &Map<String, Value>) -> Result<Self, serde_json::Error> {
+        let value = Value::Object(map.clone());
+        serde_json::from_value(value)
+    }
+}
+
+
+/// Converts `every` into a step expressed in the physical unit of `dtype`.
+///
+/// Integers are taken as-is (e.g. raw epoch timestamps). Duration strings
+/// such as `"15m"` are accepted for `Datetime` and `Date` columns, and for
+/// integer epoch columns when their `time_unit` (s, ms, us or ns) is given.
+pub fn physical_step(
+    every: &Value,
+    dtype: &DataType,
+    time_unit: Option<&str>
+) -> Result<i64, Box<dyn std::error::Error>> {
+    if let Some(step) = every.as_i64() {
+        return Ok(step)
+    }
+
+    let every = match every.as_str() {
+        Some(every) => every,
+        None => {
+            return Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("every must be an integer or a duration string"))))
+        }
+    };
+
+    let duration = Duration::try_parse(every)?;
+    if !duration.is_constant_duration(None) {
+        return Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("every must be a constant duration"))))
+    }
+
+    let nanoseconds = duration.duration_ns();
+    match (dtype, time_unit) {
+        (DataType::Datetime(TimeUnit::Nanoseconds, _), _) => Ok(nanoseconds),
+        (DataType::Datetime(TimeUnit::Microseconds, _), _) => Ok(nanoseconds / 1_000),
+        (DataType::Datetime(TimeUnit::Milliseconds, _), _) => Ok(nanoseconds / 1_000_000),
+        (DataType::Date, _) => Ok(nanoseconds / 86_400_000_000_000),
+        (dtype, Some(time_unit)) if dtype.is_integer() => match time_unit {
+            "ns" => Ok(nanoseconds),
+            "us" => Ok(nanoseconds / 1_000),
+            "ms" => Ok(nanoseconds / 1_000_000),
+            "s" => Ok(nanoseconds / 1_000_000_000),
+            _ => Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("Invalid time_unit, expected s, ms, us or ns"))))
+        },
+        (dtype, None) if dtype.is_integer() => Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("Duration strings on integer columns require a time_unit")))),
+        _ => Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("Duration strings require a temporal column"))))
+    }
+}
+
+
+/// Upsamples `time_column` to a fixed `every` frequency per `by` group, from
+/// the first to the last timestamp of each group, then fills the new rows.
+///
+/// The grid is aligned on multiples of `every` since the epoch, so feeds
+/// resampled with the same `every` share their timestamps whatever their
+/// first observation. Rows are snapped down to the grid, the last row of each
+/// interval being kept, so the output is a regular grid and `linear`
+/// interpolates by time. Only the rows added by the grid are filled, nulls of
+/// the source being kept. On integer epoch columns, a duration string `every`
+/// such as `"15m"` is converted with `time_unit` (s, ms, us or ns).
+pub fn resample(
+    mut lf: LazyFrame,
+    parameters: &ResampleRawParameters
+) -> Result<LazyFrame, Box<dyn std::error::Error>> {
+    let time_column = parameters.time_column.as_str();
+    let by: Vec<String> = parameters.by.clone().unwrap_or_default();
+
+    let schema = lf.collect_schema()?;
+    let dtype: DataType = match schema.get(time_column) {
+        Some(dtype) => dtype.clone(),
+        None => {
+            return Err(Box::new(PolarsError::ColumnNotFound(ErrString::new_static("time_column not found in feed"))))
+        }
+    };
+
+    let step = physical_step(&parameters.every, &dtype, parameters.time_unit.as_deref())?;
+    if step <= 0 {
+        return Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("every must be strictly positive"))))
+    }
+
+    let mut keys: Vec<String> = by.clone();
+    keys.push(time_column.to_string());
+    let key_exprs: Vec<Expr> = keys.iter().map(col).collect();
+
+    let physical = col(time_column).to_physical().cast(DataType::Int64);
+    let snapped: LazyFrame = lf
+        .sort(keys.clone(), SortMultipleOptions::default().with_maintain_order(true))
+        .with_columns([
+            (physical.floor_div(lit(step)) * lit(step))
+                .cast(dtype.to_physical())
+                .cast(dtype.clone())
+                .alias(time_column),
+            lit(true).alias("_observed")
+        ])
+        .unique_stable(Some(keys.iter().map(|key| key.into()).collect()), UniqueKeepStrategy::Last);
+
+    let physical = col(time_column).to_physical().cast(DataType::Int64);
+    let grid_expr: Expr = int_range(
+        physical.clone().min(),
+        physical.max() + lit(1),
+        step,
+        DataType::Int64
+    )
+        .cast(dtype.to_physical())
+        .cast(dtype)
+        .alias(time_column);
+
+    let grid: LazyFrame = if by.is_empty() {
+        snapped.clone().select([grid_expr])
+    } else {
+        snapped.clone()
+            .group_by(by.iter().map(col).collect::<Vec<Expr>>())
+            .agg([grid_expr])
+            .explode([time_column])
+    };
+
+    let mut resampled: LazyFrame = grid
+        .join(snapped, &key_exprs, &key_exprs, JoinArgs::new(JoinType::Left))
+        .sort(keys.clone(), SortMultipleOptions::default().with_maintain_order(true));
+
+    let strategy = parameters.strategy.clone().unwrap_or("null".to_string());
+    if strategy != "null" {
+        let columns: Vec<String> = match &parameters.columns {
+            Some(columns) => columns.clone(),
+            None => schema
+                .iter_names()
+                .filter(|name| !keys.iter().any(|key| key.as_str() == name.as_str()))
+                .map(|name| name.to_string())
+                .collect()
+        };
+
+        let mut exprs: Vec<Expr> = Vec::with_capacity(columns.len());
+        for column in columns.iter() {
+            let mut filled = fill_null_expr(col(column), &strategy, parameters.value.as_ref())?;
+            if !by.is_empty() {
+                filled = filled.over(by.iter().map(col).collect::<Vec<Expr>>());
+            }
+            exprs.push(
+                when(col("_observed").is_null())
+                    .then(filled)
+                    .otherwise(col(column))
+                    .alias(column)
+            );
+        }
+        resampled = resampled.with_columns(exprs);
+    }
+
+    Ok(resampled.drop(["_observed"]))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resample_fills_only_rows_added_by_the_grid() {
+        let lf = df!(
+            "t" => [1i64, 3, 8, 9],
+            "x" => [Some(9.0f64), None, Some(-1.0), Some(8.0)],
+            "y" => [1i64, 2, 3, 4]
+        ).unwrap().lazy();
+        let parameters = ResampleRawParameters {
+            time_column: "t".to_string(),
+            every: Value::from(2),
+            by: None,
+            strategy: Some("linear".to_string()),
+            value: None,
+            columns: Some(vec!["x".to_string()]),
+            time_unit: None
+        };
+
+        let df = resample(lf, &parameters).unwrap().collect().unwrap();
+
+        // 1 and 3 snap down to 0 and 2, 9 replaces 8 as the last row of [8, 10)
+        assert_eq!(df.column("t").unwrap().i64().unwrap().into_no_null_iter().collect::<Vec<i64>>(), vec![0, 2, 4, 6, 8]);
+        assert_eq!(
+            df.column("x").unwrap().f64().unwrap().into_iter().collect::<Vec<Option<f64>>>(),
+            vec![Some(9.0), None, Some(8.5), Some(8.25), Some(8.0)]
+        );
+        assert_eq!(
+            df.column("y").unwrap().i64().unwrap().into_iter().collect::<Vec<Option<i64>>>(),
+            vec![Some(1), Some(2), None, None, Some(4)]
+        );
+    }
+
+    #[test]
+    fn physical_step_requires_time_unit_on_integer_columns() {
+        let every = Value::from("15m");
+        assert!(physical_step(&every, &DataType::Int64, None).is_err());
+        assert_eq!(physical_step(&every, &DataType::Int64, Some("ms")).unwrap(), 900_000);
+        assert_eq!(physical_step(&every, &DataType::Int64, Some("s")).unwrap(), 900);
+        assert_eq!(physical_step(&Value::from(5), &DataType::Int64, None).unwrap(), 5);
+    }
+}
@@ -0,0 +1,69 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+
+use polars::{error::ErrString, prelude::*};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map,Value};
+
+#[allow(unused)]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SampleRawParameters {
+    pub n: Option<u64>,
+    pub fraction: Option<f64>,
+    pub seed: Option<u64>,
+    pub by: Option<Vec<String>>,
+    pub with_replacement: Option<bool>
+}
+
+impl SampleRawParameters {
+    pub fn from_map(map: &Map<String, Value>) -> Result<Self, serde_json::Error> {
+        let value = Value::Object(map.clone());
+        serde_json::from_value(value)
+    }
+}
+
+
+/// Samples `n` rows or a `fraction` of the rows, reproducibly when a `seed`
+/// is given. With `by`, sampling is stratified: `n` rows or `fraction` of the
+/// rows are drawn from each group.
+///
+/// Every column is sampled with the same seed, so that they all draw the same
+/// rows; without a `seed`, one is picked at random for the call.
+pub fn sample(
+    lf: LazyFrame,
+    parameters: &SampleRawParameters
+) -> Result<LazyFrame, Box<dyn std::error::Error>> {
+    let with_replacement = parameters.with_replacement.unwrap_or(false);
+    let seed = Some(parameters.seed.unwrap_or_else(|| RandomState::new().build_hasher().finish()));
+
+    let sampled: Expr = match (parameters.n, parameters.fraction) {
+        (Some(n), None) => {
+            // Without replacement, groups smaller than n are kept whole
+            let n: Expr = if with_replacement {
+                lit(n)
+            } else {
+                when(len().cast(DataType::UInt64).lt(lit(n)))
+                    .then(len().cast(DataType::UInt64))
+                    .otherwise(lit(n))
+            };
+            all().sample_n(n, with_replacement, false, seed)
+        },
+        (None, Some(fraction)) => all().sample_frac(lit(fraction), with_replacement, false, seed),
+        _ => {
+            return Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("Exactly one of n or fraction is expected"))))
+        }
+    };
+
+    let by: Vec<String> = parameters.by.clone().unwrap_or_default();
+    if by.is_empty() {
+        return Ok(lf.select([sampled]))
+    }
+
+    let partition: Vec<Expr> = by.iter().map(col).collect();
+    Ok(
+        lf
+            .sort(by, SortMultipleOptions::default().with_maintain_order(true))
+            .select([sampled.over_with_options(partition, None, WindowMapping::Explode)])
+    )
+}
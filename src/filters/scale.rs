@@ -0,0 +1,76 @@
+use std::collections::HashMap;
+
+use polars::{error::ErrString, prelude::*};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map,Value};
+
+#[allow(unused)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ScaleStatistics {
+    pub center: f64,
+    pub scale: f64
+}
+
+#[allow(unused)]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ScaleRawParameters {
+    pub columns: Vec<String>,
+    pub method: String,
+    pub statistics: Option<HashMap<String, ScaleStatistics>>
+}
+
+impl ScaleRawParameters {
+    pub fn from_map(map: &Map<String, Value>) -> Result<Self, serde_json::Error> {
+        let value = Value::Object(map.clone());
+        serde_json::from_value(value)
+    }
+}
+
+
+/// Center and scale of `column` computed over the whole feed for `method`:
+/// mean and standard deviation (zscore), minimum and range (minmax), median
+/// and interquartile range (robust).
+fn computed_statistics(
+    column: &str,
+    method: &str
+) -> Result<(Expr, Expr), Box<dyn std::error::Error>> {
+    let values = col(column).cast(DataType::Float64);
+
+    match method {
+        "zscore" => Ok((values.clone().mean(), values.std(1))),
+        "minmax" => Ok((values.clone().min(), values.clone().max() - values.min())),
+        "robust" => Ok((
+            values.clone().median(),
+            values.clone().quantile(lit(0.75), QuantileMethod::Linear)
+                - values.quantile(lit(0.25), QuantileMethod::Linear)
+        )),
+        _ => Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("Invalid scaling method"))))
+    }
+}
+
+
+/// Rescales `columns` in place as `(x - center) / scale`. Statistics are
+/// computed over the feed unless supplied explicitly for a column, so that
+/// exports can reuse the statistics of a training set.
+pub fn scale(
+    lf: LazyFrame,
+    parameters: &ScaleRawParameters
+) -> Result<LazyFrame, Box<dyn std::error::Error>> {
+    let statistics = parameters.statistics.clone().unwrap_or_default();
+
+    let mut exprs: Vec<Expr> = Vec::with_capacity(parameters.columns.len());
+    for column in parameters.columns.iter() {
+        let (center, scale): (Expr, Expr) = match statistics.get(column) {
+            Some(statistics) => (lit(statistics.center), lit(statistics.scale)),
+            None => computed_statistics(column, &parameters.method)?
+        };
+
+        exprs.push(
+            ((col(column).cast(DataType::Float64) - center) / scale)
+                .alias(column)
+        );
+    }
+
+    Ok(lf.with_columns(exprs))
+}
@@ -0,0 +1,98 @@
+use polars::{error::ErrString, prelude::*};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map,Value};
+
+use super::aggregation::{aggregation_expr,AggregationRawParameters};
+use super::resample::physical_step;
+
+#[allow(unused)]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SessionizeRawParameters {
+    pub time_column: String,
+    pub timeout: Value,
+    pub by: Option<Vec<String>>,
+    pub aggregate: Option<bool>,
+    pub aggregations: Option<Vec<AggregationRawParameters>>,
+    pub time_unit: Option<String>
+}
+
+impl SessionizeRawParameters {
+    pub fn from_map(map: &Map<String, Value>) -> Result<Self, serde_json::Error> {
+        let value = Value::Object(map.clone());
+        serde_json::from_value(value)
+    }
+}
+
+
+/// Appends a `session_id` to each row, starting a new session for a `by` key
+/// whenever the gap with its previous timestamp exceeds `timeout`.
+///
+/// When `aggregate` is set (or `aggregations` are given), each session is
+/// reduced to `(key, session_id, start, end, row_count, ...aggregations)`.
+///
+/// On integer epoch columns, a duration string `timeout` such as `"30m"` is
+/// converted with `time_unit` (s, ms, us or ns).
+pub fn sessionize(
+    mut lf: LazyFrame,
+    parameters: &SessionizeRawParameters
+) -> Result<LazyFrame, Box<dyn std::error::Error>> {
+    let time_column = parameters.time_column.as_str();
+    let by: Vec<String> = parameters.by.clone().unwrap_or_default();
+
+    let schema = lf.collect_schema()?;
+    let dtype: DataType = match schema.get(time_column) {
+        Some(dtype) => dtype.clone(),
+        None => {
+            return Err(Box::new(PolarsError::ColumnNotFound(ErrString::new_static("time_column not found in feed"))))
+        }
+    };
+    let timeout = physical_step(&parameters.timeout, &dtype, parameters.time_unit.as_deref())?;
+
+    let mut sort_columns: Vec<String> = by.clone();
+    sort_columns.push(time_column.to_string());
+
+    let partition: Vec<Expr> = by.iter().map(col).collect();
+    let physical = col(time_column).to_physical().cast(DataType::Int64);
+    let mut gap: Expr = physical.clone() - physical.shift(lit(1));
+    if !partition.is_empty() {
+        gap = gap.over(&partition);
+    }
+
+    let new_session: Expr = gap.clone().gt(lit(timeout)).or(gap.is_null());
+    let mut session_id: Expr = col("_new_session").cast(DataType::UInt32).cum_sum(false);
+    if !partition.is_empty() {
+        session_id = session_id.over(&partition);
+    }
+
+    // Window expressions cannot be nested, hence the intermediate column
+    let sessionized = lf
+        .sort(sort_columns, SortMultipleOptions::default().with_maintain_order(true))
+        .with_columns([new_session.alias("_new_session")])
+        .with_columns([session_id.alias("session_id")])
+        .drop(["_new_session"]);
+
+    let aggregations = parameters.aggregations.clone().unwrap_or_default();
+    if !parameters.aggregate.unwrap_or(!aggregations.is_empty()) {
+        return Ok(sessionized)
+    }
+
+    let mut aggs: Vec<Expr> = vec![
+        col(time_column).min().alias("start"),
+        col(time_column).max().alias("end"),
+        len().alias("row_count")
+    ];
+    for aggregation in aggregations.iter() {
+        aggs.push(aggregation_expr(aggregation)?);
+    }
+
+    let mut keys: Vec<String> = by.clone();
+    keys.push("session_id".to_string());
+
+    Ok(
+        sessionized
+            .group_by(keys.iter().map(col).collect::<Vec<Expr>>())
+            .agg(aggs)
+            .sort(keys, SortMultipleOptions::default())
+    )
+}
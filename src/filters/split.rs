@@ -0,0 +1,106 @@
+use polars::{error::ErrString, prelude::*};
+use sha2::{Digest, Sha256};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map,Value};
+
+#[allow(unused)]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SplitRawParameters {
+    pub columns: Vec<String>,
+    pub ratios: Vec<f64>,
+    pub labels: Option<Vec<String>>,
+    pub seed: Option<u64>,
+    pub alias: Option<String>
+}
+
+impl SplitRawParameters {
+    pub fn from_map(map: &Map<String, Value>) -> Result<Self, serde_json::Error> {
+        let value = Value::Object(map.clone());
+        serde_json::from_value(value)
+    }
+}
+
+
+/// Position of every key in [0, 1), from the first 53 bits of the SHA-256 of
+/// `seed` followed by the key, so it is stable across versions and platforms.
+fn bucket_expr(
+    key: Expr,
+    seed: u64
+) -> Expr {
+    key.map(
+        move |column| {
+            let buckets: Float64Chunked = column
+                .str()?
+                .apply_nonnull_values_generic(DataType::Float64, |value| {
+                    let mut hasher = Sha256::new();
+                    hasher.update(seed.to_le_bytes());
+                    hasher.update(value.as_bytes());
+                    let digest = hasher.finalize();
+                    let mut prefix = [0u8; 8];
+                    prefix.copy_from_slice(&digest[..8]);
+                    (u64::from_be_bytes(prefix) >> 11) as f64 / (1u64 << 53) as f64
+                });
+            Ok(Some(buckets.with_name(column.name().clone()).into_column()))
+        },
+        GetOutput::from_type(DataType::Float64)
+    )
+}
+
+
+/// Appends a `split` column assigning each row to train/validation/test
+/// according to `ratios`, from a hash of the key `columns`.
+///
+/// The assignment only depends on the key values and the `seed`, so a key
+/// stays in the same split across runs and as the feed grows.
+pub fn split(
+    lf: LazyFrame,
+    parameters: &SplitRawParameters
+) -> Result<LazyFrame, Box<dyn std::error::Error>> {
+    let labels: Vec<String> = parameters.labels.clone().unwrap_or(vec![
+        "train".to_string(),
+        "validation".to_string(),
+        "test".to_string()
+    ]);
+
+    if parameters.columns.is_empty() {
+        return Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("At least one key column is expected"))))
+    }
+    if parameters.ratios.is_empty() || parameters.ratios.len() > labels.len() {
+        return Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("One ratio per split label is expected"))))
+    }
+    if parameters.ratios.iter().any(|ratio| *ratio < 0.0) {
+        return Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("Ratios must be positive"))))
+    }
+    let total: f64 = parameters.ratios.iter().sum();
+    if total <= 0.0 {
+        return Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("Ratios must not all be zero"))))
+    }
+
+    let seed = parameters.seed.unwrap_or(0);
+    let keys: Vec<Expr> = parameters.columns
+        .iter()
+        .map(|column| col(column).cast(DataType::String))
+        .collect();
+
+    let bucket: Expr = bucket_expr(concat_str(keys, "\u{1f}", false), seed);
+
+    let last = parameters.ratios.len() - 1;
+    let mut thresholds: Vec<f64> = Vec::with_capacity(last);
+    let mut cumulative = 0.0;
+    for ratio in parameters.ratios.iter().take(last) {
+        cumulative += ratio / total;
+        thresholds.push(cumulative);
+    }
+
+    let mut split_expr: Expr = lit(labels[last].clone());
+    for (threshold, label) in thresholds.iter().zip(labels.iter()).rev() {
+        split_expr = when(bucket.clone().lt(lit(*threshold)))
+            .then(lit(label.clone()))
+            .otherwise(split_expr);
+    }
+
+    let alias = parameters.alias.clone().unwrap_or("split".to_string());
+
+    Ok(lf.with_columns([split_expr.alias(alias)]))
+}
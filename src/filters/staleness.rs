@@ -0,0 +1,118 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use polars::{error::ErrString, prelude::*};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map,Value};
+
+use super::resample::physical_step;
+
+#[allow(unused)]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StalenessRawParameters {
+    pub time_column: String,
+    pub by: Option<Vec<String>>,
+    pub reference: Option<Value>,
+    pub time_unit: Option<String>,
+    pub threshold: Option<Value>
+}
+
+impl StalenessRawParameters {
+    pub fn from_map(map: &Map<String, Value>) -> Result<Self, serde_json::Error> {
+        let value = Value::Object(map.clone());
+        serde_json::from_value(value)
+    }
+}
+
+
+/// Current time expressed in the physical unit of `dtype`. Integer columns are
+/// raw epochs whose unit is given by `time_unit` (s, ms, us or ns).
+fn now_physical(
+    dtype: &DataType,
+    time_unit: Option<&str>
+) -> Result<i64, Box<dyn std::error::Error>> {
+    let since_epoch = SystemTime::now().duration_since(UNIX_EPOCH)?;
+
+    let unit = match dtype {
+        DataType::Datetime(TimeUnit::Nanoseconds, _) => "ns",
+        DataType::Datetime(TimeUnit::Microseconds, _) => "us",
+        DataType::Datetime(TimeUnit::Milliseconds, _) => "ms",
+        DataType::Date => "d",
+        _ => match time_unit {
+            Some(time_unit) => time_unit,
+            None => {
+                return Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("A \"now\" reference on integer columns requires a time_unit"))))
+            }
+        }
+    };
+
+    match unit {
+        "ns" => Ok(since_epoch.as_nanos() as i64),
+        "us" => Ok(since_epoch.as_micros() as i64),
+        "ms" => Ok(since_epoch.as_millis() as i64),
+        "s" => Ok(since_epoch.as_secs() as i64),
+        "d" => Ok((since_epoch.as_secs() / 86_400) as i64),
+        _ => Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("Invalid time_unit"))))
+    }
+}
+
+
+/// Summarises, per `by` key, the last timestamp seen and how long ago it was
+/// relative to `reference` (a physical timestamp, or "now" by default).
+///
+/// On integer epoch columns, `time_unit` (s, ms, us or ns) is required to
+/// compare with "now" and to convert duration string thresholds such as
+/// `"15m"`, as for the other time steps.
+pub fn staleness(
+    mut lf: LazyFrame,
+    parameters: &StalenessRawParameters
+) -> Result<LazyFrame, Box<dyn std::error::Error>> {
+    let time_column = parameters.time_column.as_str();
+    let by: Vec<String> = parameters.by.clone().unwrap_or_default();
+
+    let schema = lf.collect_schema()?;
+    let dtype: DataType = match schema.get(time_column) {
+        Some(dtype) => dtype.clone(),
+        None => {
+            return Err(Box::new(PolarsError::ColumnNotFound(ErrString::new_static("time_column not found in feed"))))
+        }
+    };
+
+    let time_unit = parameters.time_unit.as_deref();
+    let reference: i64 = match &parameters.reference {
+        None => now_physical(&dtype, time_unit)?,
+        Some(Value::String(reference)) if reference == "now" => now_physical(&dtype, time_unit)?,
+        Some(reference) => match reference.as_i64() {
+            Some(reference) => reference,
+            None => {
+                return Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("reference must be \"now\" or an integer timestamp"))))
+            }
+        }
+    };
+
+    let last_seen: Expr = col(time_column).max().alias("last_seen");
+    let summary: LazyFrame = if by.is_empty() {
+        lf.select([last_seen])
+    } else {
+        lf
+            .group_by(by.iter().map(col).collect::<Vec<Expr>>())
+            .agg([last_seen])
+    };
+
+    let reference_expr: Expr = lit(reference).cast(dtype.to_physical()).cast(dtype.clone());
+    let physical_staleness: Expr = lit(reference) - col("last_seen").to_physical().cast(DataType::Int64);
+
+    let mut summary = summary
+        .with_columns([reference_expr.alias("reference")])
+        .with_columns([(col("reference") - col("last_seen")).alias("staleness")]);
+
+    if let Some(threshold) = &parameters.threshold {
+        let threshold = physical_step(threshold, &dtype, time_unit)?;
+        summary = summary.with_columns([physical_staleness.gt(lit(threshold)).alias("is_stale")]);
+    }
+
+    let mut sort_columns: Vec<String> = by.clone();
+    sort_columns.push("last_seen".to_string());
+
+    Ok(summary.sort(sort_columns, SortMultipleOptions::default()))
+}
@@ -0,0 +1,142 @@
+use polars::{error::ErrString, prelude::*};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map,Value};
+
+#[allow(unused)]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TemporalRawParameters {
+    pub column: String,
+    pub format: Option<String>,
+    pub r#type: Option<String>,
+    pub time_unit: Option<String>,
+    pub replace_time_zone: Option<String>,
+    pub time_zone: Option<String>,
+    pub truncate: Option<String>,
+    pub round: Option<String>,
+    pub extract: Option<Vec<String>>,
+    pub alias: Option<String>
+}
+
+impl TemporalRawParameters {
+    pub fn from_map(map: &Map<String, Value>) -> Result<Self, serde_json::Error> {
+        let value = Value::Object(map.clone());
+        serde_json::from_value(value)
+    }
+}
+
+
+/// Decodes `column` into a temporal expression: strings are parsed with
+/// `format` (inferred when missing) and integers are read as epochs in
+/// `time_unit` (s, ms, us or ns, ms by default).
+fn decode_expr(
+    column: &str,
+    dtype: &DataType,
+    parameters: &TemporalRawParameters
+) -> Result<Expr, Box<dyn std::error::Error>> {
+    let to_date = parameters.r#type.as_deref() == Some("date");
+
+    match dtype {
+        DataType::String => {
+            let options = StrptimeOptions {
+                format: parameters.format.clone().map(|format| format.into()),
+                ..Default::default()
+            };
+            if to_date {
+                Ok(col(column).str().to_date(options))
+            } else {
+                Ok(col(column).str().to_datetime(None, None, options, lit("raise")))
+            }
+        },
+        dtype if dtype.is_integer() => {
+            let epoch = col(column).cast(DataType::Int64);
+            let datetime = match parameters.time_unit.as_deref().unwrap_or("ms") {
+                "s" => (epoch * lit(1_000)).cast(DataType::Datetime(TimeUnit::Milliseconds, None)),
+                "ms" => epoch.cast(DataType::Datetime(TimeUnit::Milliseconds, None)),
+                "us" => epoch.cast(DataType::Datetime(TimeUnit::Microseconds, None)),
+                "ns" => epoch.cast(DataType::Datetime(TimeUnit::Nanoseconds, None)),
+                _ => {
+                    return Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("Invalid time_unit, expected s, ms, us or ns"))))
+                }
+            };
+            Ok(if to_date { datetime.cast(DataType::Date) } else { datetime })
+        },
+        DataType::Datetime(_, _) if to_date => Ok(col(column).cast(DataType::Date)),
+        DataType::Datetime(_, _) | DataType::Date => Ok(col(column)),
+        _ => Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("temporal expects a string, integer or temporal column"))))
+    }
+}
+
+
+fn component_expr(
+    expr: Expr,
+    component: &str
+) -> Result<Expr, Box<dyn std::error::Error>> {
+    match component {
+        "year" => Ok(expr.dt().year()),
+        "iso_year" => Ok(expr.dt().iso_year()),
+        "quarter" => Ok(expr.dt().quarter()),
+        "month" => Ok(expr.dt().month()),
+        "week" => Ok(expr.dt().week()),
+        "day" => Ok(expr.dt().day()),
+        "weekday" => Ok(expr.dt().weekday()),
+        "ordinal_day" => Ok(expr.dt().ordinal_day()),
+        "hour" => Ok(expr.dt().hour()),
+        "minute" => Ok(expr.dt().minute()),
+        "second" => Ok(expr.dt().second()),
+        "date" => Ok(expr.dt().date()),
+        _ => Err(Box::new(PolarsError::InvalidOperation(format!("Unknown component {component}").into())))
+    }
+}
+
+
+/// Turns `column` into a `Datetime` (or a `Date` with `type: "date"`), then
+/// optionally sets its time zone with `replace_time_zone`, converts it to
+/// `time_zone`, and truncates or rounds it to an interval such as `"15m"`.
+///
+/// Each `extract` component (year, iso_year, quarter, month, week, day,
+/// weekday, ordinal_day, hour, minute, second, date) is added as a
+/// `{alias}_{component}` column. ISO weeks and weekdays start on Monday.
+pub fn temporal(
+    mut lf: LazyFrame,
+    parameters: &TemporalRawParameters
+) -> Result<LazyFrame, Box<dyn std::error::Error>> {
+    let column = parameters.column.as_str();
+    let alias = parameters.alias.clone().unwrap_or(column.to_string());
+
+    let schema = lf.collect_schema()?;
+    let dtype: DataType = match schema.get(column) {
+        Some(dtype) => dtype.clone(),
+        None => {
+            return Err(Box::new(PolarsError::ColumnNotFound(ErrString::new_static("column not found in feed"))))
+        }
+    };
+
+    let mut expr = decode_expr(column, &dtype, parameters)?;
+
+    if let Some(time_zone) = &parameters.replace_time_zone {
+        expr = expr.dt().replace_time_zone(Some(time_zone.into()), lit("raise"), NonExistent::Raise);
+    }
+    if let Some(time_zone) = &parameters.time_zone {
+        expr = expr.dt().convert_time_zone(time_zone.into());
+    }
+    if let Some(every) = &parameters.truncate {
+        expr = expr.dt().truncate(lit(every.clone()));
+    }
+    if let Some(every) = &parameters.round {
+        expr = expr.dt().round(lit(every.clone()));
+    }
+
+    let mut components: Vec<Expr> = Vec::new();
+    for component in parameters.extract.clone().unwrap_or_default().iter() {
+        components.push(
+            component_expr(col(alias.as_str()), component)?.alias(format!("{alias}_{component}"))
+        );
+    }
+
+    Ok(
+        lf
+            .with_columns([expr.alias(alias.as_str())])
+            .with_columns(components)
+    )
+}
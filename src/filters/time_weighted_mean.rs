@@ -0,0 +1,114 @@
+use polars::{error::ErrString, prelude::*};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map,Value};
+
+use super::ohlc::bucket_options;
+
+#[allow(unused)]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TimeWeightedMeanRawParameters {
+    pub time_column: String,
+    pub columns: Vec<String>,
+    pub by: Option<Vec<String>>,
+    pub every: Option<Value>,
+    pub period: Option<Value>,
+    pub offset: Option<Value>,
+    pub label: Option<String>,
+    pub closed: Option<String>,
+    pub start_by: Option<String>,
+    pub time_unit: Option<String>
+}
+
+impl TimeWeightedMeanRawParameters {
+    pub fn from_map(map: &Map<String, Value>) -> Result<Self, serde_json::Error> {
+        let value = Value::Object(map.clone());
+        serde_json::from_value(value)
+    }
+}
+
+
+/// Aggregation weighting each value of `column` by the time elapsed until the
+/// next timestamp of the group. The last value of a group has no successor and
+/// gets no weight; groups holding a single value fall back to the plain mean.
+///
+/// Rows are expected to be sorted by `time_column` within each group.
+pub fn time_weighted_mean_expr(
+    column: &str,
+    time_column: &str
+) -> Expr {
+    let physical = col(time_column).to_physical().cast(DataType::Float64);
+    let weight: Expr = physical.clone().shift(lit(-1)) - physical;
+    let weighted: Expr = col(column).cast(DataType::Float64) * weight.clone();
+    let total_weight: Expr = weight.filter(weighted.clone().is_not_null()).sum();
+
+    when(total_weight.clone().gt(lit(0.0)))
+        .then(weighted.sum() / total_weight)
+        .otherwise(col(column).cast(DataType::Float64).mean())
+}
+
+
+/// Computes the time-weighted mean of `columns` per `by` key, and per time
+/// bucket when `every` is given, as `{column}_time_weighted_mean` columns.
+/// Duration strings on integer epoch time columns need their `time_unit`.
+pub fn time_weighted_mean(
+    lf: LazyFrame,
+    parameters: &TimeWeightedMeanRawParameters
+) -> Result<LazyFrame, Box<dyn std::error::Error>> {
+    let time_column = parameters.time_column.as_str();
+    let by: Vec<String> = parameters.by.clone().unwrap_or_default();
+    let partition: Vec<Expr> = by.iter().map(col).collect();
+
+    let aggs: Vec<Expr> = parameters.columns
+        .iter()
+        .map(|column| {
+            time_weighted_mean_expr(column, time_column)
+                .alias(format!("{column}_time_weighted_mean"))
+        })
+        .collect();
+
+    let mut sort_columns: Vec<String> = by.clone();
+    sort_columns.push(time_column.to_string());
+    let mut sorted = lf.sort(sort_columns.clone(), SortMultipleOptions::default().with_maintain_order(true));
+
+    match &parameters.every {
+        Some(every) => {
+            let schema = sorted.collect_schema()?;
+            let dtype: DataType = match schema.get(time_column) {
+                Some(dtype) => dtype.clone(),
+                None => {
+                    return Err(Box::new(PolarsError::ColumnNotFound(ErrString::new_static("time_column not found in feed"))))
+                }
+            };
+
+            let options = bucket_options(
+                &dtype,
+                parameters.time_unit.as_deref(),
+                every,
+                parameters.period.as_ref(),
+                parameters.offset.as_ref(),
+                parameters.label.as_ref(),
+                parameters.closed.as_ref(),
+                parameters.start_by.as_ref()
+            )?;
+
+            Ok(
+                sorted
+                    .group_by_dynamic(col(time_column), partition, options)
+                    .agg(aggs)
+                    .sort(sort_columns, SortMultipleOptions::default())
+            )
+        },
+        None => {
+            if partition.is_empty() {
+                return Ok(sorted.select(aggs))
+            }
+
+            Ok(
+                sorted
+                    .group_by_stable(partition)
+                    .agg(aggs)
+            )
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use polars::prelude::*;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map,Value};
+
+#[allow(unused)]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UnnestRawParameters {
+    pub columns: Vec<String>,
+    pub separator: Option<String>
+}
+
+impl UnnestRawParameters {
+    pub fn from_map(map: &Map<String, Value>) -> Result<Self, serde_json::Error> {
+        let value = Value::Object(map.clone());
+        serde_json::from_value(value)
+    }
+}
+
+
+/// Replaces struct `columns` by one column per field. With a `separator`,
+/// fields are prefixed by their struct name (e.g. `payload.value`) to avoid
+/// clashes with existing columns.
+pub fn unnest(
+    lf: LazyFrame,
+    parameters: &UnnestRawParameters
+) -> Result<LazyFrame, Box<dyn std::error::Error>> {
+    let lf = match &parameters.separator {
+        Some(separator) => lf.with_columns(
+            parameters
+                .columns
+                .iter()
+                .map(|column| col(column).name().prefix_fields(&format!("{column}{separator}")))
+                .collect::<Vec<Expr>>()
+        ),
+        None => lf
+    };
+
+    Ok(lf.unnest(&parameters.columns))
+}
@@ -0,0 +1,100 @@
+use polars::{error::ErrString, prelude::*, series::ops::NullBehavior};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map,Value};
+
+#[allow(unused)]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WindowFunctionRawParameters {
+    pub function: String,
+    pub column: Option<String>,
+    pub periods: Option<i64>,
+    pub alias: Option<String>
+}
+
+#[allow(unused)]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WindowRawParameters {
+    pub by: Option<Vec<String>>,
+    pub order_by: String,
+    pub windows: Vec<WindowFunctionRawParameters>
+}
+
+impl WindowRawParameters {
+    pub fn from_map(map: &Map<String, Value>) -> Result<Self, serde_json::Error> {
+        let value = Value::Object(map.clone());
+        serde_json::from_value(value)
+    }
+}
+
+
+fn window_expr(
+    window: &WindowFunctionRawParameters
+) -> Result<Expr, Box<dyn std::error::Error>> {
+    let function = window.function.as_str();
+
+    if function == "row_number" {
+        let alias = window.alias.clone().unwrap_or("row_number".to_string());
+        return Ok(
+            (int_range(lit(0), len(), 1, IDX_DTYPE) + lit(1))
+                .alias(alias)
+        )
+    }
+
+    let column = match &window.column {
+        Some(column) => column,
+        None => {
+            return Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("column is expected for this window function"))))
+        }
+    };
+    let periods = window.periods.unwrap_or(1);
+
+    let expr: Expr = match function {
+        "shift" => col(column).shift(lit(periods)),
+        "lag" => col(column).shift(lit(periods)),
+        "lead" => col(column).shift(lit(-periods)),
+        "diff" => col(column).diff(periods, NullBehavior::Ignore),
+        "pct_change" => col(column).pct_change(lit(periods)),
+        "cum_sum" => col(column).cum_sum(false),
+        "cum_max" => col(column).cum_max(false),
+        "cum_count" => col(column).cum_count(false),
+        _ => {
+            return Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("Invalid window function"))))
+        }
+    };
+
+    let alias = window.alias.clone().unwrap_or(format!("{column}_{function}"));
+
+    Ok(expr.alias(alias))
+}
+
+
+/// Appends lag/lead, diff, pct_change, cumulative aggregates and row numbers
+/// computed per `by` partition, rows being ordered by `order_by`.
+pub fn window(
+    lf: LazyFrame,
+    parameters: &WindowRawParameters
+) -> Result<LazyFrame, Box<dyn std::error::Error>> {
+    let by: Vec<String> = parameters.by.clone().unwrap_or_default();
+
+    let mut sort_columns: Vec<String> = by.clone();
+    sort_columns.push(parameters.order_by.clone());
+
+    let partition: Vec<Expr> = by.iter().map(col).collect();
+
+    let mut exprs: Vec<Expr> = Vec::with_capacity(parameters.windows.len());
+    for window in parameters.windows.iter() {
+        let expr = window_expr(window)?;
+        if partition.is_empty() {
+            exprs.push(expr);
+        } else {
+            exprs.push(expr.over(&partition));
+        }
+    }
+
+    Ok(
+        lf
+            .sort(sort_columns, SortMultipleOptions::default().with_maintain_order(true))
+            .with_columns(exprs)
+    )
+}
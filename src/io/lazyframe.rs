@@ -1,6 +1,126 @@
-use std::io::Cursor;
+use std::io::{Cursor, Write};
 
-use polars::prelude::*;
+use polars::{error::ErrString, prelude::*};
+use polars::export::arrow::io::ipc::write::{StreamWriter, WriteOptions};
+
+/// Rows collected and written per record batch of an Arrow stream.
+const STREAM_BATCH_ROWS: usize = 1 << 16;
+
+
+/// Formats a feed can be written to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OutputFormat {
+    Table,
+    Csv,
+    Ndjson,
+    Json,
+    Parquet,
+    Arrow,
+    ArrowStream
+}
+
+impl OutputFormat {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "table" | "text" => Some(Self::Table),
+            "csv" => Some(Self::Csv),
+            "ndjson" | "jsonl" => Some(Self::Ndjson),
+            "json" => Some(Self::Json),
+            "parquet" => Some(Self::Parquet),
+            "arrow" | "ipc" => Some(Self::Arrow),
+            "arrow_stream" | "arrows" => Some(Self::ArrowStream),
+            _ => None
+        }
+    }
+
+    pub fn from_mime(mime: &str) -> Option<Self> {
+        match mime.to_lowercase().as_str() {
+            "text/plain" => Some(Self::Table),
+            "text/csv" => Some(Self::Csv),
+            "application/x-ndjson" | "application/jsonl" => Some(Self::Ndjson),
+            "application/json" => Some(Self::Json),
+            "application/vnd.apache.parquet" | "application/x-parquet" => Some(Self::Parquet),
+            "application/vnd.apache.arrow.file" => Some(Self::Arrow),
+            "application/vnd.apache.arrow.stream" => Some(Self::ArrowStream),
+            _ => None
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Table => "text/plain; charset=utf-8",
+            Self::Csv => "text/csv; charset=utf-8",
+            Self::Ndjson => "application/x-ndjson",
+            Self::Json => "application/json",
+            Self::Parquet => "application/vnd.apache.parquet",
+            Self::Arrow => "application/vnd.apache.arrow.file",
+            Self::ArrowStream => "application/vnd.apache.arrow.stream"
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Table => "txt",
+            Self::Csv => "csv",
+            Self::Ndjson => "ndjson",
+            Self::Json => "json",
+            Self::Parquet => "parquet",
+            Self::Arrow => "arrow",
+            Self::ArrowStream => "arrows"
+        }
+    }
+
+    /// Text formats are worth compressing over HTTP, binary ones carry their
+    /// own compression.
+    pub fn is_text(&self) -> bool {
+        matches!(self, Self::Table | Self::Csv | Self::Ndjson | Self::Json)
+    }
+}
+
+
+/// Writer options, `orient` applies to JSON (records or columns),
+/// `compression` to Parquet and Arrow, `compression_level` to the Parquet
+/// codecs that support it (gzip, brotli and zstd).
+#[derive(Debug, Default)]
+pub struct OutputOptions {
+    pub orient: Option<String>,
+    pub compression: Option<String>,
+    pub compression_level: Option<i32>
+}
+
+impl OutputOptions {
+    /// Rejects unknown options before the feed gets collected.
+    pub fn check(&self, format: OutputFormat) -> PolarsResult<()> {
+        if let Some(orient) = &self.orient && orient != "records" && orient != "columns" {
+            return Err(PolarsError::InvalidOperation(ErrString::new_static("Invalid orient, expected records or columns")))
+        }
+        match format {
+            OutputFormat::Parquet => {
+                self.parquet_compression()?;
+            },
+            OutputFormat::Arrow | OutputFormat::ArrowStream => {
+                self.ipc_compression()?;
+            },
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    pub fn parquet_compression(&self) -> PolarsResult<ParquetCompression> {
+        match &self.compression {
+            Some(compression) => parquet_compression(compression, self.compression_level),
+            None => Ok(ParquetCompression::default())
+        }
+    }
+
+    pub fn ipc_compression(&self) -> PolarsResult<Option<IpcCompression>> {
+        match &self.compression {
+            Some(compression) => ipc_compression(compression),
+            None => Ok(None)
+        }
+    }
+}
 
 
 #[allow(unused)]
@@ -21,14 +141,234 @@ pub fn lazyframe_as_str(
 
 
 #[allow(unused)]
-pub fn lazyframe_as_arrowbytes(lf: LazyFrame) -> PolarsResult<Vec<u8>> {
+pub fn lazyframe_as_arrowbytes(
+    lf: LazyFrame,
+    compression: Option<IpcCompression>
+) -> PolarsResult<Vec<u8>> {
     let mut buffer = Vec::new();
     let mut cursor = Cursor::new(&mut buffer);
 
     let mut df: DataFrame = lf.collect()?;
 
     IpcWriter::new(&mut cursor)
+        .with_compression(compression)
+        .finish(&mut df)?;
+
+    Ok(buffer)
+}
+
+
+/// Writes the feed to `writer` as an Arrow IPC stream, one record batch at
+/// a time.
+///
+/// The plan is executed once, on the streaming engine where it supports the
+/// plan, and the result is written in record batches of at most
+/// `STREAM_BATCH_ROWS` rows, in the order of the feed.
+pub fn lazyframe_as_arrowstream<W: Write>(
+    lf: LazyFrame,
+    writer: W,
+    compression: Option<IpcCompression>
+) -> PolarsResult<()> {
+    let write_options = WriteOptions {
+        compression: compression.map(|compression| compression.into())
+    };
+    let mut stream = StreamWriter::new(writer, write_options);
+
+    let df: DataFrame = lf.with_streaming(true).collect()?;
+    stream.start(&df.schema().to_arrow(CompatLevel::newest()), None)?;
+
+    let mut offset: usize = 0;
+    while offset < df.height() {
+        let mut batch = df.slice(offset as i64, STREAM_BATCH_ROWS);
+        batch.align_chunks();
+        for chunk in batch.iter_chunks(CompatLevel::newest(), true) {
+            stream.write(&chunk, None)?;
+        }
+        offset += batch.height();
+    }
+
+    stream.finish()?;
+    stream.into_inner().flush()?;
+
+    Ok(())
+}
+
+
+pub fn lazyframe_as_csv(lf: LazyFrame) -> PolarsResult<Vec<u8>> {
+    let mut buffer = Vec::new();
+    let mut df: DataFrame = lf.collect()?;
+
+    CsvWriter::new(&mut buffer)
+        .include_header(true)
+        .finish(&mut df)?;
+
+    Ok(buffer)
+}
+
+
+pub fn lazyframe_as_ndjson(lf: LazyFrame) -> PolarsResult<Vec<u8>> {
+    let mut buffer = Vec::new();
+    let mut df: DataFrame = lf.collect()?;
+
+    JsonWriter::new(&mut buffer)
+        .with_json_format(JsonFormat::JsonLines)
         .finish(&mut df)?;
 
     Ok(buffer)
 }
+
+
+/// Writes the feed as a JSON array of row objects (`records`, the default)
+/// or as a single object of column arrays (`columns`).
+pub fn lazyframe_as_json(
+    lf: LazyFrame,
+    orient: &str
+) -> PolarsResult<Vec<u8>> {
+    let mut buffer = Vec::new();
+
+    match orient {
+        "records" => {
+            let mut df: DataFrame = lf.collect()?;
+            JsonWriter::new(&mut buffer)
+                .with_json_format(JsonFormat::Json)
+                .finish(&mut df)?;
+        },
+        "columns" => {
+            // One row holding every column as a list is written as one object
+            let mut df: DataFrame = lf.select([all().implode()]).collect()?;
+            JsonWriter::new(&mut buffer)
+                .with_json_format(JsonFormat::JsonLines)
+                .finish(&mut df)?;
+            while buffer.last() == Some(&b'\n') {
+                buffer.pop();
+            }
+        },
+        _ => {
+            return Err(PolarsError::InvalidOperation(ErrString::new_static("Invalid orient, expected records or columns")))
+        }
+    }
+
+    Ok(buffer)
+}
+
+
+/// Parquet codec from its name, with an optional level for gzip (0-9),
+/// brotli (0-11) and zstd (1-22).
+pub fn parquet_compression(
+    name: &str,
+    level: Option<i32>
+) -> PolarsResult<ParquetCompression> {
+    let compression = match (name, level) {
+        ("uncompressed", None) => ParquetCompression::Uncompressed,
+        ("snappy", None) => ParquetCompression::Snappy,
+        ("lz4", None) => ParquetCompression::Lz4Raw,
+        ("gzip", level) => ParquetCompression::Gzip(level.map(|level| GzipLevel::try_new(level as u8)).transpose()?),
+        ("brotli", level) => ParquetCompression::Brotli(level.map(|level| BrotliLevel::try_new(level as u32)).transpose()?),
+        ("zstd", level) => ParquetCompression::Zstd(level.map(ZstdLevel::try_new).transpose()?),
+        ("uncompressed" | "snappy" | "lz4", Some(_)) => {
+            return Err(PolarsError::InvalidOperation(ErrString::new_static("compression_level is only supported by gzip, brotli and zstd")))
+        },
+        _ => {
+            return Err(PolarsError::InvalidOperation(ErrString::new_static("Invalid compression, expected uncompressed, snappy, gzip, brotli, zstd or lz4")))
+        }
+    };
+
+    Ok(compression)
+}
+
+
+/// Arrow IPC buffer compression from its name.
+pub fn ipc_compression(name: &str) -> PolarsResult<Option<IpcCompression>> {
+    match name {
+        "uncompressed" => Ok(None),
+        "lz4" => Ok(Some(IpcCompression::LZ4)),
+        "zstd" => Ok(Some(IpcCompression::ZSTD)),
+        _ => Err(PolarsError::InvalidOperation(ErrString::new_static("Invalid compression, expected uncompressed, lz4 or zstd")))
+    }
+}
+
+
+pub fn lazyframe_as_parquet(
+    lf: LazyFrame,
+    compression: ParquetCompression
+) -> PolarsResult<Vec<u8>> {
+    let mut buffer = Vec::new();
+    let mut df: DataFrame = lf.collect()?;
+
+    ParquetWriter::new(&mut buffer)
+        .with_compression(compression)
+        .finish(&mut df)?;
+
+    Ok(buffer)
+}
+
+
+/// Writes the feed in `format`.
+pub fn lazyframe_as_format(
+    lf: LazyFrame,
+    format: OutputFormat,
+    options: &OutputOptions
+) -> PolarsResult<Vec<u8>> {
+    match format {
+        OutputFormat::Table => Ok(lazyframe_as_str(lf)?.into_bytes()),
+        OutputFormat::Csv => lazyframe_as_csv(lf),
+        OutputFormat::Ndjson => lazyframe_as_ndjson(lf),
+        OutputFormat::Json => lazyframe_as_json(lf, options.orient.as_deref().unwrap_or("records")),
+        OutputFormat::Parquet => lazyframe_as_parquet(lf, options.parquet_compression()?),
+        OutputFormat::Arrow => lazyframe_as_arrowbytes(lf, options.ipc_compression()?),
+        OutputFormat::ArrowStream => {
+            let mut buffer = Vec::new();
+            lazyframe_as_arrowstream(lf, &mut buffer, options.ipc_compression()?)?;
+            Ok(buffer)
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    #[test]
+    fn arrowstream_executes_plan_once_and_keeps_order() {
+        let rows = STREAM_BATCH_ROWS * 3 + 10;
+        let executions = Arc::new(AtomicUsize::new(0));
+        let counter = executions.clone();
+        let lf = df!("x" => (0..rows as i64).collect::<Vec<i64>>())
+            .unwrap()
+            .lazy()
+            .sort(["x"], SortMultipleOptions::default().with_order_descending(true))
+            .map(
+                move |df| {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    Ok(df)
+                },
+                AllowedOptimizations::default(),
+                None,
+                Some("count_executions")
+            );
+
+        let mut bytes: Vec<u8> = Vec::new();
+        lazyframe_as_arrowstream(lf, &mut bytes, None).unwrap();
+        assert_eq!(executions.load(Ordering::SeqCst), 1);
+
+        let df = IpcStreamReader::new(Cursor::new(bytes)).finish().unwrap();
+        let expected: Vec<i64> = (0..rows as i64).rev().collect();
+        assert_eq!(df.column("x").unwrap().i64().unwrap().into_no_null_iter().collect::<Vec<i64>>(), expected);
+    }
+
+    #[test]
+    fn arrowstream_writes_empty_feed() {
+        let lf = df!("x" => Vec::<i64>::new()).unwrap().lazy();
+
+        let mut bytes: Vec<u8> = Vec::new();
+        lazyframe_as_arrowstream(lf, &mut bytes, None).unwrap();
+
+        let df = IpcStreamReader::new(Cursor::new(bytes)).finish().unwrap();
+        assert_eq!(df.height(), 0);
+        assert_eq!(df.get_column_names(), ["x"]);
+    }
+}
\ No newline at end of file
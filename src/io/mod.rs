@@ -1 +1,5 @@
 pub(crate) mod lazyframe;
+pub(crate) mod plan;
+pub(crate) mod render;
+pub(crate) mod schema;
+pub(crate) mod stream;
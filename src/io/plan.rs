@@ -0,0 +1,81 @@
+use polars::prelude::*;
+
+use serde::Serialize;
+
+
+/// Unoptimized and optimized plans of a feed, as text and as Graphviz DOT.
+#[derive(Debug, Serialize)]
+pub struct PlanDescription {
+    pub plan: String,
+    pub optimized_plan: String,
+    pub dot: String,
+    pub optimized_dot: String
+}
+
+
+/// Execution window of one node of the physical plan, in microseconds since
+/// the start of the query.
+#[derive(Debug, Serialize)]
+pub struct NodeTiming {
+    pub node: String,
+    pub start: u64,
+    pub end: u64,
+    pub duration: u64
+}
+
+
+/// Profile of a feed run: the shape of its result and the timing of every
+/// node that was executed.
+#[derive(Debug, Serialize)]
+pub struct Profile {
+    pub height: usize,
+    pub width: usize,
+    pub nodes: Vec<NodeTiming>
+}
+
+
+/// Describes the plans of the feed without executing it.
+pub fn lazyframe_plans(lf: &LazyFrame) -> PolarsResult<PlanDescription> {
+    Ok(
+        PlanDescription {
+            plan: lf.explain(false)?,
+            optimized_plan: lf.explain(true)?,
+            dot: lf.to_dot(false)?,
+            optimized_dot: lf.to_dot(true)?
+        }
+    )
+}
+
+
+/// Runs the feed with node timing enabled. The result itself is dropped.
+pub fn lazyframe_profile(lf: LazyFrame) -> PolarsResult<Profile> {
+    let (df, timings) = lf.profile()?;
+
+    let nodes = timings.column("node")?.str()?;
+    let starts = timings.column("start")?.cast(&DataType::UInt64)?;
+    let ends = timings.column("end")?.cast(&DataType::UInt64)?;
+
+    let nodes: Vec<NodeTiming> = nodes
+        .iter()
+        .zip(starts.u64()?.iter())
+        .zip(ends.u64()?.iter())
+        .map(|((node, start), end)| {
+            let start = start.unwrap_or(0);
+            let end = end.unwrap_or(start);
+            NodeTiming {
+                node: node.unwrap_or_default().to_string(),
+                start,
+                end,
+                duration: end.saturating_sub(start)
+            }
+        })
+        .collect();
+
+    Ok(
+        Profile {
+            height: df.height(),
+            width: df.width(),
+            nodes
+        }
+    )
+}
@@ -0,0 +1,287 @@
+use comfy_table::presets::{ASCII_MARKDOWN, UTF8_FULL_CONDENSED};
+use comfy_table::{ContentArrangement, Table};
+use polars::{error::ErrString, prelude::*};
+
+
+const ELLIPSIS: &str = "…";
+
+
+/// Renderer of `/display` tables.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderAs {
+    Text,
+    Markdown,
+    Html
+}
+
+impl RenderAs {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "text" => Some(Self::Text),
+            "markdown" | "md" => Some(Self::Markdown),
+            "html" => Some(Self::Html),
+            _ => None
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Text => "text/plain; charset=utf-8",
+            Self::Markdown => "text/markdown; charset=utf-8",
+            Self::Html => "text/html; charset=utf-8"
+        }
+    }
+}
+
+
+/// Rendering limits and number formatting, defaulting to polars' own display
+/// defaults: 10 rows, 8 columns and 30 characters per value.
+#[derive(Debug, Clone, Default)]
+pub struct RenderOptions {
+    pub max_rows: Option<usize>,
+    pub max_cols: Option<usize>,
+    pub max_str_len: Option<usize>,
+    pub float_precision: Option<usize>,
+    pub thousands_separator: Option<String>
+}
+
+impl RenderOptions {
+    /// Checks the options before the feed is collected.
+    pub fn check(&self) -> PolarsResult<()> {
+        self.thousands_separator().map(|_| ())
+    }
+
+    fn thousands_separator(&self) -> PolarsResult<Option<char>> {
+        match self.thousands_separator.as_deref() {
+            None | Some("") => Ok(None),
+            Some(separator) => {
+                let mut chars = separator.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(separator), None) => Ok(Some(separator)),
+                    _ => Err(PolarsError::InvalidOperation(ErrString::new_static("thousands_separator is expected to be a single character")))
+                }
+            }
+        }
+    }
+}
+
+
+/// Inserts `separator` between groups of three digits of the integer part.
+fn group_thousands(value: &str, separator: char) -> String {
+    let (sign, unsigned) = match value.strip_prefix('-') {
+        Some(unsigned) => ("-", unsigned),
+        None => ("", value)
+    };
+    let digits_end = unsigned.find(|c: char| !c.is_ascii_digit()).unwrap_or(unsigned.len());
+    let (digits, rest) = unsigned.split_at(digits_end);
+
+    let mut grouped = String::with_capacity(value.len() + digits.len() / 3);
+    grouped.push_str(sign);
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            grouped.push(separator);
+        }
+        grouped.push(digit);
+    }
+    grouped.push_str(rest);
+    grouped
+}
+
+
+fn truncate(value: &str, max_len: usize) -> String {
+    if value.chars().count() <= max_len {
+        return value.to_string()
+    }
+    let mut truncated: String = value.chars().take(max_len).collect();
+    truncated.push_str(ELLIPSIS);
+    truncated
+}
+
+
+fn cell(
+    column: &Column,
+    row: usize,
+    max_str_len: usize,
+    float_precision: Option<usize>,
+    thousands_separator: Option<char>
+) -> PolarsResult<String> {
+    let value = column.get(row)?;
+    let formatted = match value {
+        AnyValue::Null => "null".to_string(),
+        AnyValue::Float32(_) | AnyValue::Float64(_) => {
+            let formatted = match (float_precision, value.extract::<f64>()) {
+                (Some(precision), Some(float)) => format!("{float:.precision$}"),
+                _ => format!("{value}")
+            };
+            match thousands_separator {
+                Some(separator) => group_thousands(&formatted, separator),
+                None => formatted
+            }
+        },
+        _ if column.dtype().is_integer() => match thousands_separator {
+            Some(separator) => group_thousands(&format!("{value}"), separator),
+            None => format!("{value}")
+        },
+        _ => value.str_value().into_owned()
+    };
+
+    Ok(truncate(&formatted, max_str_len))
+}
+
+
+/// Row indices to display, `None` standing for the elided middle rows.
+fn displayed_rows(height: usize, max_rows: usize) -> Vec<Option<usize>> {
+    if height <= max_rows {
+        return (0..height).map(Some).collect()
+    }
+    if max_rows == 0 {
+        return vec![None]
+    }
+
+    let half = max_rows / 2;
+    let mut rows: Vec<Option<usize>> = (0..(half + max_rows % 2)).map(Some).collect();
+    rows.push(None);
+    rows.extend(((height - half)..height).map(Some));
+    rows
+}
+
+
+/// Column indices to display, `None` standing for the elided middle columns.
+fn displayed_columns(width: usize, max_cols: usize) -> Vec<Option<usize>> {
+    if width <= max_cols {
+        return (0..width).map(Some).collect()
+    }
+
+    let mut columns: Vec<Option<usize>> = (0..max_cols.div_ceil(2)).map(Some).collect();
+    columns.push(None);
+    columns.extend(((width - max_cols / 2)..width).map(Some));
+    columns
+}
+
+
+fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+
+/// Keeps a value within its Markdown table cell.
+fn escape_markdown(value: &str) -> String {
+    value
+        .replace('|', "\\|")
+        .replace("\r\n", "<br>")
+        .replace('\n', "<br>")
+}
+
+
+/// Renders `df` as a text table laid out like polars' display, a Markdown
+/// table or an HTML table, eliding the middle rows and columns beyond the
+/// limits of `options`.
+pub fn render_dataframe(
+    df: &DataFrame,
+    render_as: RenderAs,
+    options: &RenderOptions
+) -> PolarsResult<String> {
+    let max_str_len = options.max_str_len.unwrap_or(30);
+    let thousands_separator = options.thousands_separator()?;
+
+    let columns = displayed_columns(df.width(), options.max_cols.unwrap_or(8));
+    let rows = displayed_rows(df.height(), options.max_rows.unwrap_or(10));
+
+    let names: Vec<String> = columns
+        .iter()
+        .map(|column| match column {
+            Some(i) => truncate(df.get_columns()[*i].name(), max_str_len),
+            None => ELLIPSIS.to_string()
+        })
+        .collect();
+    let dtypes: Vec<String> = columns
+        .iter()
+        .map(|column| match column {
+            Some(i) => format!("{}", df.get_columns()[*i].dtype()),
+            None => String::new()
+        })
+        .collect();
+
+    let mut cells: Vec<Vec<String>> = Vec::with_capacity(rows.len());
+    for row in rows.iter() {
+        let mut row_cells: Vec<String> = Vec::with_capacity(columns.len());
+        for column in columns.iter() {
+            row_cells.push(match (row, column) {
+                (Some(row), Some(column)) => cell(
+                    &df.get_columns()[*column],
+                    *row,
+                    max_str_len,
+                    options.float_precision,
+                    thousands_separator
+                )?,
+                _ => ELLIPSIS.to_string()
+            });
+        }
+        cells.push(row_cells);
+    }
+
+    let shape = format!("shape: ({}, {})", df.height(), df.width());
+
+    match render_as {
+        RenderAs::Text | RenderAs::Markdown => {
+            let mut table = Table::new();
+            table.set_content_arrangement(ContentArrangement::Disabled);
+            if render_as == RenderAs::Text {
+                table.load_preset(UTF8_FULL_CONDENSED);
+                table.set_header(
+                    names
+                        .iter()
+                        .zip(dtypes.iter())
+                        .map(|(name, dtype)| if dtype.is_empty() { name.clone() } else { format!("{name}\n---\n{dtype}") })
+                );
+                table.add_rows(cells);
+            } else {
+                table.load_preset(ASCII_MARKDOWN);
+                table.set_header(names.iter().map(|name| escape_markdown(name)));
+                table.add_rows(cells.iter().map(|row_cells| row_cells.iter().map(|value| escape_markdown(value))));
+            }
+
+            if render_as == RenderAs::Markdown {
+                return Ok(format!("{shape}\n\n{table}\n"))
+            }
+            Ok(format!("{shape}\n{table}"))
+        },
+        RenderAs::Html => {
+            let mut html = String::new();
+            html.push_str(&format!("<div>\n<small>{shape}</small>\n<table>\n<thead>\n<tr>"));
+            for name in names.iter() {
+                html.push_str(&format!("<th>{}</th>", escape_html(name)));
+            }
+            html.push_str("</tr>\n<tr>");
+            for dtype in dtypes.iter() {
+                html.push_str(&format!("<td>{}</td>", escape_html(dtype)));
+            }
+            html.push_str("</tr>\n</thead>\n<tbody>\n");
+            for row_cells in cells.iter() {
+                html.push_str("<tr>");
+                for value in row_cells.iter() {
+                    html.push_str(&format!("<td>{}</td>", escape_html(value)));
+                }
+                html.push_str("</tr>\n");
+            }
+            html.push_str("</tbody>\n</table>\n</div>\n");
+
+            Ok(html)
+        }
+    }
+}
+
+
+/// Collects the feed and renders it with `render_dataframe`.
+pub fn lazyframe_as_rendered(
+    lf: LazyFrame,
+    render_as: RenderAs,
+    options: &RenderOptions
+) -> PolarsResult<String> {
+    let df: DataFrame = lf.collect()?;
+    render_dataframe(&df, render_as, options)
+}
@@ -0,0 +1,38 @@
+use polars::prelude::*;
+
+use serde::Serialize;
+
+
+/// One output column of a feed.
+#[derive(Debug, Serialize)]
+pub struct ColumnSchema {
+    pub name: String,
+    pub dtype: String,
+    pub arrow_dtype: String,
+    pub nullable: bool
+}
+
+
+/// Resolves the output schema of the feed from its plan, without executing it.
+///
+/// Arrow types are those written by the Arrow outputs. Polars does not track
+/// nullability, so every column is reported as nullable, as in the Arrow
+/// schemas it writes.
+pub fn lazyframe_schema(mut lf: LazyFrame) -> PolarsResult<Vec<ColumnSchema>> {
+    let schema = lf.collect_schema()?;
+
+    Ok(
+        schema
+            .iter_fields()
+            .map(|field| {
+                let arrow_field = field.to_arrow(CompatLevel::newest());
+                ColumnSchema {
+                    name: field.name().to_string(),
+                    dtype: format!("{}", field.dtype()),
+                    arrow_dtype: format!("{:?}", arrow_field.dtype()),
+                    nullable: arrow_field.is_nullable
+                }
+            })
+            .collect()
+    )
+}
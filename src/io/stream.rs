@@ -0,0 +1,49 @@
+use std::io::{self, Write};
+
+use actix_web::web::Bytes;
+use tokio::sync::mpsc::Sender;
+
+const CHUNK_SIZE: usize = 1 << 16;
+
+
+/// Forwards everything written to a response body channel, in chunks of
+/// about `CHUNK_SIZE` bytes. Sends block, so it is meant to be used from a
+/// blocking task; writes fail once the client went away.
+pub struct ChannelWriter {
+    sender: Sender<Result<Bytes, io::Error>>,
+    buffer: Vec<u8>
+}
+
+impl ChannelWriter {
+    pub fn new(sender: Sender<Result<Bytes, io::Error>>) -> Self {
+        Self {
+            sender,
+            buffer: Vec::with_capacity(CHUNK_SIZE)
+        }
+    }
+
+    fn send_buffer(&mut self) -> io::Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(())
+        }
+
+        let chunk = Bytes::from(std::mem::replace(&mut self.buffer, Vec::with_capacity(CHUNK_SIZE)));
+        self.sender
+            .blocking_send(Ok(chunk))
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "Response body has been closed"))
+    }
+}
+
+impl Write for ChannelWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        if self.buffer.len() >= CHUNK_SIZE {
+            self.send_buffer()?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.send_buffer()
+    }
+}
@@ -1,4 +1,5 @@
 use actix_web::{App, HttpServer};
+use actix_web::middleware::Compress;
 use actix_cors::Cors;
 
 mod utils;
@@ -9,7 +10,7 @@ mod filters;
 mod fs;
 mod io;
 
-use services::api::{display_feed,download_feed,new_temp_feed};
+use services::api::{display_feed,download_feed,new_temp_feed,page_feed,feed_schema,temp_feed_schema,explain_feed,profile_feed};
 
 #[cfg(not(target_env = "msvc"))]
 use tikv_jemallocator::Jemalloc;
@@ -25,9 +26,15 @@ async fn main() -> std::io::Result<()> {
         let cors = Cors::permissive();
         App::new()
             .wrap(cors)
+            .wrap(Compress::default())
             .service(display_feed)
             .service(download_feed)
+            .service(page_feed)
             .service(new_temp_feed)
+            .service(feed_schema)
+            .service(temp_feed_schema)
+            .service(explain_feed)
+            .service(profile_feed)
     })
     .bind(("0.0.0.0".to_string(), 3000))?
     .run()
@@ -0,0 +1,84 @@
+use polars::{error::ErrString, prelude::*};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map,Value};
+
+#[allow(unused)]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CrossCorrelationRawParameters {
+    pub left_on: String,
+    pub right_on: String,
+    pub left_column: String,
+    pub right_column: String,
+    pub method: Option<String>,
+    pub min_lag: Option<i64>,
+    pub max_lag: Option<i64>
+}
+
+impl CrossCorrelationRawParameters {
+    pub fn from_map(map: &Map<String, Value>) -> Result<Self, serde_json::Error> {
+        let value = Value::Object(map.clone());
+        serde_json::from_value(value)
+    }
+}
+
+
+/// Correlates `left_column` with `right_column` shifted by each lag in
+/// `[min_lag, max_lag]`, once both feeds are aligned on their timestamp.
+///
+/// Lags are expressed in rows of the aligned feed: a positive lag compares
+/// left readings with earlier right readings, i.e. right leading left.
+/// Returns `(lag, coefficient, n)` where `n` is the number of complete pairs.
+pub fn cross_correlation(
+    feed_left: LazyFrame,
+    feed_right: LazyFrame,
+    parameters: &CrossCorrelationRawParameters
+) -> Result<LazyFrame, Box<dyn std::error::Error>> {
+    let max_lag = parameters.max_lag.unwrap_or(10);
+    let min_lag = parameters.min_lag.unwrap_or(-max_lag);
+    if min_lag > max_lag {
+        return Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("min_lag must not exceed max_lag"))))
+    }
+
+    let method = parameters.method.clone().unwrap_or("pearson".to_string());
+    let coefficient: Expr = match method.as_str() {
+        "pearson" => pearson_corr(col("left"), col("right"), 1),
+        "spearman" => spearman_rank_corr(col("left"), col("right"), 1, false),
+        _ => {
+            return Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("Invalid correlation method"))))
+        }
+    };
+
+    let aligned: LazyFrame = feed_left
+        .select([
+            col(parameters.left_on.as_str()).alias("timestamp"),
+            col(parameters.left_column.as_str()).cast(DataType::Float64).alias("left")
+        ])
+        .join(
+            feed_right.select([
+                col(parameters.right_on.as_str()).alias("timestamp"),
+                col(parameters.right_column.as_str()).cast(DataType::Float64).alias("right")
+            ]),
+            [col("timestamp")],
+            [col("timestamp")],
+            JoinArgs::new(JoinType::Full).with_coalesce(JoinCoalesce::CoalesceColumns)
+        )
+        .sort(["timestamp"], SortMultipleOptions::default())
+        .cache();
+
+    let lags: Vec<LazyFrame> = (min_lag..=max_lag)
+        .map(|lag| {
+            aligned
+                .clone()
+                .select([col("left"), col("right").shift(lit(lag))])
+                .drop_nulls(None)
+                .select([
+                    lit(lag).cast(DataType::Int64).alias("lag"),
+                    coefficient.clone().alias("coefficient"),
+                    len().alias("n")
+                ])
+        })
+        .collect();
+
+    Ok(concat(lags, UnionArgs::default())?)
+}
@@ -0,0 +1,244 @@
+use polars::{error::ErrString, prelude::*};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map,Value};
+
+#[allow(unused)]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FuzzyJoinRawParameters {
+    pub left_on: String,
+    pub right_on: String,
+    pub by: Option<Vec<String>>,
+    pub method: Option<String>,
+    pub threshold: Option<f64>,
+    pub ignore_case: Option<bool>,
+    pub suffix: Option<String>
+}
+
+impl FuzzyJoinRawParameters {
+    pub fn from_map(map: &Map<String, Value>) -> Result<Self, serde_json::Error> {
+        let value = Value::Object(map.clone());
+        serde_json::from_value(value)
+    }
+}
+
+
+/// Levenshtein distance normalized into a similarity between 0 and 1.
+fn levenshtein_similarity(a: &[char], b: &[char]) -> f64 {
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0
+    }
+
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current: Vec<usize> = vec![0; b.len() + 1];
+    for (i, char_a) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, char_b) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(char_a != char_b);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    1.0 - previous[b.len()] as f64 / longest as f64
+}
+
+
+/// Jaro-Winkler similarity, with the usual 0.1 prefix scale over at most
+/// four leading characters.
+fn jaro_winkler_similarity(a: &[char], b: &[char]) -> f64 {
+    if a.is_empty() && b.is_empty() {
+        return 1.0
+    }
+    if a.is_empty() || b.is_empty() {
+        return 0.0
+    }
+
+    let window = (a.len().max(b.len()) / 2).saturating_sub(1);
+    let mut matched_a = vec![false; a.len()];
+    let mut matched_b = vec![false; b.len()];
+    let mut matches = 0usize;
+    for (i, char_a) in a.iter().enumerate() {
+        let start = i.saturating_sub(window);
+        let end = (i + window + 1).min(b.len());
+        for j in start..end {
+            if !matched_b[j] && b[j] == *char_a {
+                matched_a[i] = true;
+                matched_b[j] = true;
+                matches += 1;
+                break;
+            }
+        }
+    }
+    if matches == 0 {
+        return 0.0
+    }
+
+    let matched_chars_b: Vec<&char> = b.iter().zip(matched_b.iter()).filter(|(_, m)| **m).map(|(c, _)| c).collect();
+    let transpositions = a
+        .iter()
+        .zip(matched_a.iter())
+        .filter(|(_, m)| **m)
+        .zip(matched_chars_b.iter())
+        .filter(|((char_a, _), char_b)| *char_a != **char_b)
+        .count() as f64 / 2.0;
+
+    let matches = matches as f64;
+    let jaro = (matches / a.len() as f64 + matches / b.len() as f64 + (matches - transpositions) / matches) / 3.0;
+
+    let prefix = a.iter().zip(b.iter()).take(4).take_while(|(char_a, char_b)| char_a == char_b).count() as f64;
+    jaro + prefix * 0.1 * (1.0 - jaro)
+}
+
+
+/// Matches every `left_on` value to the most similar `right_on` value of the
+/// same `by` block, keeping the best candidate whose score reaches
+/// `threshold`, then joins the matching right rows.
+///
+/// Scores are either a normalized Levenshtein similarity (`levenshtein`, the
+/// default) or `jaro_winkler`, both between 0 and 1. Candidates are scored on
+/// the distinct keys of both feeds, so a `by` blocking key keeps the number of
+/// comparisons tractable. Scoring is part of the plan and only runs when the
+/// feed is collected. Left rows without a match are kept with nulls.
+pub fn fuzzy_join(
+    feed_left: LazyFrame,
+    feed_right: LazyFrame,
+    parameters: &FuzzyJoinRawParameters
+) -> Result<LazyFrame, Box<dyn std::error::Error>> {
+    let left_on = parameters.left_on.as_str();
+    let right_on = parameters.right_on.as_str();
+    let by: Vec<String> = parameters.by.clone().unwrap_or_default();
+    let threshold = parameters.threshold.unwrap_or(0.8);
+    let ignore_case = parameters.ignore_case.unwrap_or(true);
+    let suffix = parameters.suffix.clone().unwrap_or("_right".to_string());
+
+    let similarity: fn(&[char], &[char]) -> f64 = match parameters.method.as_deref().unwrap_or("levenshtein") {
+        "levenshtein" => levenshtein_similarity,
+        "jaro_winkler" => jaro_winkler_similarity,
+        _ => {
+            return Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("Invalid method, expected levenshtein or jaro_winkler"))))
+        }
+    };
+
+    let by_exprs: Vec<Expr> = by.iter().map(col).collect();
+    let mut left_keys: Vec<Expr> = by_exprs.clone();
+    left_keys.push(col(left_on).cast(DataType::String).alias("_left_key"));
+    let mut right_keys: Vec<Expr> = by_exprs.clone();
+    right_keys.push(col(right_on).cast(DataType::String).alias("_right_key"));
+
+    let distinct_left = feed_left.clone().select(left_keys).unique(None, UniqueKeepStrategy::Any);
+    let distinct_right = feed_right.clone().select(right_keys).unique(None, UniqueKeepStrategy::Any);
+
+    let candidates: LazyFrame = if by.is_empty() {
+        distinct_left.cross_join(distinct_right, None)
+    } else {
+        distinct_left.join(distinct_right, &by_exprs, &by_exprs, JoinArgs::new(JoinType::Inner))
+    };
+
+    let score: Expr = as_struct(vec![col("_left_key"), col("_right_key")])
+        .map(
+            move |keys| {
+                let fields = keys.struct_()?.fields_as_series();
+                let normalize = |value: &str| -> Vec<char> {
+                    if ignore_case {
+                        value.trim().to_lowercase().chars().collect()
+                    } else {
+                        value.trim().chars().collect()
+                    }
+                };
+
+                let scores: Float64Chunked = fields[0]
+                    .str()?
+                    .iter()
+                    .zip(fields[1].str()?.iter())
+                    .map(|(left, right)| match (left, right) {
+                        (Some(left), Some(right)) => Some(similarity(&normalize(left), &normalize(right))),
+                        _ => None
+                    })
+                    .collect();
+
+                Ok(Some(scores.with_name(keys.name().clone()).into_column()))
+            },
+            GetOutput::from_type(DataType::Float64)
+        )
+        .alias("score");
+
+    let mut match_keys: Vec<String> = by.clone();
+    match_keys.push("_left_key".to_string());
+
+    let best_matches: LazyFrame = candidates
+        .with_columns([score])
+        .filter(col("score").gt_eq(lit(threshold)))
+        .sort(
+            ["score", "_right_key"],
+            SortMultipleOptions::default().with_order_descending_multi([true, false])
+        )
+        .unique_stable(Some(match_keys.iter().map(|key| key.into()).collect()), UniqueKeepStrategy::First);
+
+    let match_join_keys: Vec<Expr> = match_keys.iter().map(col).collect();
+    let mut right_join_keys: Vec<Expr> = by_exprs;
+    right_join_keys.push(col("_right_key"));
+
+    Ok(
+        feed_left
+            .with_columns([col(left_on).cast(DataType::String).alias("_left_key")])
+            .join(
+                best_matches,
+                &match_join_keys,
+                &match_join_keys,
+                JoinArgs::new(JoinType::Left)
+            )
+            .join(
+                feed_right.with_columns([col(right_on).cast(DataType::String).alias("_right_key")]),
+                &right_join_keys,
+                &right_join_keys,
+                JoinArgs::new(JoinType::Left).with_suffix(Some(suffix.into()))
+            )
+            .drop(["_left_key", "_right_key"])
+    )
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chars(value: &str) -> Vec<char> {
+        value.chars().collect()
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-4, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn levenshtein_similarity_normalizes_the_distance() {
+        assert_close(levenshtein_similarity(&chars("kitten"), &chars("sitting")), 1.0 - 3.0 / 7.0);
+        assert_close(levenshtein_similarity(&chars("same"), &chars("same")), 1.0);
+        assert_close(levenshtein_similarity(&chars("abc"), &chars("xyz")), 0.0);
+        assert_close(levenshtein_similarity(&chars("é"), &chars("e")), 0.0);
+    }
+
+    #[test]
+    fn levenshtein_similarity_of_empty_strings() {
+        assert_close(levenshtein_similarity(&chars(""), &chars("")), 1.0);
+        assert_close(levenshtein_similarity(&chars(""), &chars("abc")), 0.0);
+        assert_close(levenshtein_similarity(&chars("abc"), &chars("")), 0.0);
+    }
+
+    #[test]
+    fn jaro_winkler_similarity_rewards_common_prefixes() {
+        assert_close(jaro_winkler_similarity(&chars("MARTHA"), &chars("MARHTA")), 0.9611);
+        assert_close(jaro_winkler_similarity(&chars("DIXON"), &chars("DICKSONX")), 0.8133);
+        assert_close(jaro_winkler_similarity(&chars("same"), &chars("same")), 1.0);
+        assert_close(jaro_winkler_similarity(&chars("abc"), &chars("xyz")), 0.0);
+    }
+
+    #[test]
+    fn jaro_winkler_similarity_of_empty_strings() {
+        assert_close(jaro_winkler_similarity(&chars(""), &chars("")), 1.0);
+        assert_close(jaro_winkler_similarity(&chars(""), &chars("abc")), 0.0);
+        assert_close(jaro_winkler_similarity(&chars("abc"), &chars("")), 0.0);
+    }
+}
@@ -1,6 +1,14 @@
 pub(crate) mod join;
+pub(crate) mod cross_correlation;
+pub(crate) mod set_operations;
+pub(crate) mod reconcile;
+pub(crate) mod fuzzy_join;
 
 use join::{join,JoinRawParameters};
+use cross_correlation::{cross_correlation,CrossCorrelationRawParameters};
+use set_operations::{intersect,except,symmetric_difference,SetOperationRawParameters};
+use reconcile::{reconcile,ReconcileRawParameters};
+use fuzzy_join::{fuzzy_join,FuzzyJoinRawParameters};
 
 use polars::prelude::*;
 use polars::error::ErrString;
@@ -24,6 +32,54 @@ pub fn get_feed_from_operation(
             )?;
             return Ok(join(feed_left, feed_right, &parameters))
         },
+        "cross_correlation" => {
+            let parameters = CrossCorrelationRawParameters::from_map(
+                parameters
+                    .as_object()
+                    .unwrap()
+            )?;
+            return cross_correlation(feed_left, feed_right, &parameters)
+        },
+        "intersect" => {
+            let parameters = SetOperationRawParameters::from_map(
+                parameters
+                    .as_object()
+                    .unwrap()
+            )?;
+            return intersect(feed_left, feed_right, &parameters)
+        },
+        "except" => {
+            let parameters = SetOperationRawParameters::from_map(
+                parameters
+                    .as_object()
+                    .unwrap()
+            )?;
+            return except(feed_left, feed_right, &parameters)
+        },
+        "symmetric_difference" => {
+            let parameters = SetOperationRawParameters::from_map(
+                parameters
+                    .as_object()
+                    .unwrap()
+            )?;
+            return symmetric_difference(feed_left, feed_right, &parameters)
+        },
+        "reconcile" => {
+            let parameters = ReconcileRawParameters::from_map(
+                parameters
+                    .as_object()
+                    .unwrap()
+            )?;
+            return reconcile(feed_left, feed_right, &parameters)
+        },
+        "fuzzy_join" => {
+            let parameters = FuzzyJoinRawParameters::from_map(
+                parameters
+                    .as_object()
+                    .unwrap()
+            )?;
+            return fuzzy_join(feed_left, feed_right, &parameters)
+        },
         _ => {
             return Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("Operation is unknown"))))
         }
@@ -0,0 +1,144 @@
+use std::collections::HashMap;
+
+use polars::prelude::*;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map,Value};
+
+#[allow(unused)]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReconcileRawParameters {
+    pub on: Vec<String>,
+    pub columns: Option<Vec<String>>,
+    pub tolerance: Option<f64>,
+    pub tolerances: Option<HashMap<String, f64>>,
+    pub suffix: Option<String>
+}
+
+impl ReconcileRawParameters {
+    pub fn from_map(map: &Map<String, Value>) -> Result<Self, serde_json::Error> {
+        let value = Value::Object(map.clone());
+        serde_json::from_value(value)
+    }
+}
+
+
+/// Compares both feeds key by key through a full join and reports a
+/// `status` per key: `only_left`, `only_right`, `matched` or `mismatched`.
+///
+/// Compared columns (by default every non-key column found on both sides)
+/// are kept as `{column}` and `{column}{suffix}` pairs, and
+/// `mismatched_columns` lists the ones that differ. Numeric columns match
+/// when their absolute difference is within the column tolerance.
+pub fn reconcile(
+    mut feed_left: LazyFrame,
+    mut feed_right: LazyFrame,
+    parameters: &ReconcileRawParameters
+) -> Result<LazyFrame, Box<dyn std::error::Error>> {
+    let schema_left = feed_left.collect_schema()?;
+    let schema_right = feed_right.collect_schema()?;
+
+    for key in parameters.on.iter() {
+        schema_left.try_get(key)?;
+        schema_right.try_get(key)?;
+    }
+
+    let columns: Vec<String> = match &parameters.columns {
+        Some(columns) => columns.clone(),
+        None => schema_left
+            .iter_names()
+            .map(|name| name.to_string())
+            .filter(|name| !parameters.on.contains(name) && schema_right.contains(name))
+            .collect()
+    };
+
+    let suffix = parameters.suffix.clone().unwrap_or("_right".to_string());
+    let default_tolerance = parameters.tolerance.unwrap_or(0.0);
+    let tolerances = parameters.tolerances.clone().unwrap_or_default();
+
+    let keys: Vec<Expr> = parameters.on.iter().map(col).collect();
+    let joined = feed_left
+        .with_columns([lit(true).alias("_in_left")])
+        .join(
+            feed_right.with_columns([lit(true).alias("_in_right")]),
+            &keys,
+            &keys,
+            JoinArgs::new(JoinType::Full)
+                .with_coalesce(JoinCoalesce::CoalesceColumns)
+                .with_suffix(Some(suffix.clone().into()))
+        );
+
+    let mut differences: Vec<Expr> = Vec::with_capacity(columns.len());
+    let mut labels: Vec<Expr> = Vec::with_capacity(columns.len());
+    for column in columns.iter() {
+        let left = col(column);
+        let right = col(format!("{column}{suffix}").as_str());
+
+        let dtype = schema_left.try_get(column)?;
+        let differs: Expr = if dtype.is_numeric() {
+            let tolerance = tolerances.get(column).copied().unwrap_or(default_tolerance);
+            when(left.clone().is_null().and(right.clone().is_null()))
+                .then(lit(false))
+                .when(left.clone().is_null().or(right.clone().is_null()))
+                .then(lit(true))
+                .otherwise(
+                    (left.cast(DataType::Float64) - right.cast(DataType::Float64))
+                        .abs()
+                        .gt(lit(tolerance))
+                )
+        } else {
+            left.neq_missing(right)
+        };
+
+        let difference_name = format!("_differs_{column}");
+        differences.push(differs.alias(difference_name.as_str()));
+        labels.push(
+            when(col(difference_name.as_str()))
+                .then(lit(column.clone()))
+                .otherwise(lit(NULL).cast(DataType::String))
+        );
+    }
+
+    let any_difference: Expr = columns
+        .iter()
+        .map(|column| col(format!("_differs_{column}").as_str()))
+        .fold(lit(false), |acc, differs| acc.or(differs));
+
+    let status: Expr = when(col("_in_right").is_null())
+        .then(lit("only_left"))
+        .when(col("_in_left").is_null())
+        .then(lit("only_right"))
+        .when(any_difference)
+        .then(lit("mismatched"))
+        .otherwise(lit("matched"));
+
+    let mismatched_columns: Expr = if labels.is_empty() {
+        lit(NULL).cast(DataType::String)
+    } else {
+        concat_str(labels, ",", true)
+    };
+
+    let mut selection: Vec<Expr> = keys.clone();
+    selection.push(col("status"));
+    selection.push(col("mismatched_columns"));
+    for column in columns.iter() {
+        selection.push(col(column));
+        selection.push(col(format!("{column}{suffix}").as_str()));
+    }
+
+    Ok(
+        joined
+            .with_columns(differences)
+            .with_columns([
+                status.alias("status"),
+                mismatched_columns.alias("mismatched_columns")
+            ])
+            .with_columns([
+                when(col("status").eq(lit("mismatched")))
+                    .then(col("mismatched_columns"))
+                    .otherwise(lit(NULL).cast(DataType::String))
+                    .alias("mismatched_columns")
+            ])
+            .select(selection)
+    )
+}
@@ -0,0 +1,114 @@
+use polars::{error::ErrString, prelude::*};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map,Value};
+
+#[allow(unused)]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SetOperationRawParameters {
+    pub on: Option<Vec<String>>,
+    pub join_nulls: Option<bool>
+}
+
+impl SetOperationRawParameters {
+    pub fn from_map(map: &Map<String, Value>) -> Result<Self, serde_json::Error> {
+        let value = Value::Object(map.clone());
+        serde_json::from_value(value)
+    }
+}
+
+
+/// Resolves the comparison keys and checks both feeds agree on them: the
+/// given `on` columns must exist on both sides with the same dtype, and when
+/// comparing on all columns both schemas must be identical.
+fn comparison_keys(
+    feed_left: &mut LazyFrame,
+    feed_right: &mut LazyFrame,
+    on: Option<&Vec<String>>
+) -> Result<Vec<Expr>, Box<dyn std::error::Error>> {
+    let schema_left = feed_left.collect_schema()?;
+    let schema_right = feed_right.collect_schema()?;
+
+    let keys: Vec<String> = match on {
+        Some(on) => on.clone(),
+        None => {
+            if schema_left.len() != schema_right.len()
+                || schema_left.iter_names().zip(schema_right.iter_names()).any(|(l, r)| l != r) {
+                return Err(Box::new(PolarsError::SchemaMismatch(ErrString::new_static("Both feeds must have the same columns, in the same order, when no key is given"))))
+            }
+            schema_left.iter_names().map(|name| name.to_string()).collect()
+        }
+    };
+
+    for key in keys.iter() {
+        let dtype_left = schema_left.try_get(key)?;
+        let dtype_right = schema_right.try_get(key)?;
+        if dtype_left != dtype_right {
+            return Err(Box::new(PolarsError::SchemaMismatch(
+                format!("Column {key} is {dtype_left} on the left feed but {dtype_right} on the right feed").into()
+            )))
+        }
+    }
+
+    Ok(keys.iter().map(col).collect())
+}
+
+
+fn set_join(
+    feed_left: LazyFrame,
+    feed_right: LazyFrame,
+    keys: &[Expr],
+    how: JoinType,
+    join_nulls: bool
+) -> LazyFrame {
+    let mut args = JoinArgs::new(how);
+    args.join_nulls = join_nulls;
+
+    feed_left.join(feed_right, keys, keys, args)
+}
+
+
+/// Rows of the left feed whose key is also found in the right feed.
+pub fn intersect(
+    mut feed_left: LazyFrame,
+    mut feed_right: LazyFrame,
+    parameters: &SetOperationRawParameters
+) -> Result<LazyFrame, Box<dyn std::error::Error>> {
+    let keys = comparison_keys(&mut feed_left, &mut feed_right, parameters.on.as_ref())?;
+    let join_nulls = parameters.join_nulls.unwrap_or(true);
+
+    Ok(set_join(feed_left, feed_right, &keys, JoinType::Semi, join_nulls))
+}
+
+
+/// Rows of the left feed whose key is missing from the right feed.
+pub fn except(
+    mut feed_left: LazyFrame,
+    mut feed_right: LazyFrame,
+    parameters: &SetOperationRawParameters
+) -> Result<LazyFrame, Box<dyn std::error::Error>> {
+    let keys = comparison_keys(&mut feed_left, &mut feed_right, parameters.on.as_ref())?;
+    let join_nulls = parameters.join_nulls.unwrap_or(true);
+
+    Ok(set_join(feed_left, feed_right, &keys, JoinType::Anti, join_nulls))
+}
+
+
+/// Rows of either feed whose key is missing from the other one, tagged with
+/// a `source` column (`left` or `right`). Columns found on one side only are
+/// null for the rows of the other side.
+pub fn symmetric_difference(
+    mut feed_left: LazyFrame,
+    mut feed_right: LazyFrame,
+    parameters: &SetOperationRawParameters
+) -> Result<LazyFrame, Box<dyn std::error::Error>> {
+    let keys = comparison_keys(&mut feed_left, &mut feed_right, parameters.on.as_ref())?;
+    let join_nulls = parameters.join_nulls.unwrap_or(true);
+
+    let only_left = set_join(feed_left.clone(), feed_right.clone(), &keys, JoinType::Anti, join_nulls)
+        .with_columns([lit("left").alias("source")]);
+    let only_right = set_join(feed_right, feed_left, &keys, JoinType::Anti, join_nulls)
+        .with_columns([lit("right").alias("source")]);
+
+    Ok(concat_lf_diagonal([only_left, only_right], UnionArgs::default())?)
+}
@@ -1,22 +1,177 @@
 use std::time::Instant;
 
-use actix_web::http::header::ContentType;
-use actix_web::{get, web, HttpResponse, Responder, post};
-use actix_web::web::Json;
+use actix_web::http::header::{ContentEncoding, ContentType};
+use actix_web::{get, web, HttpRequest, HttpResponse, Responder, post};
+use actix_web::web::{Bytes, Json};
+use futures_util::stream;
+use polars::prelude::{IntoLazy,LazyFrame};
+use serde::Deserialize;
+use serde_json::{Map,Value};
 
 use crate::logger_elapsed;
 use crate::feed::{get_feed,new_temporary_feed};
 use crate::feed::parameters::FeedParameters;
-use crate::io::lazyframe::{lazyframe_as_str,lazyframe_as_arrowbytes};
+use crate::io::lazyframe::{lazyframe_as_str,lazyframe_as_format,lazyframe_as_arrowstream,OutputFormat,OutputOptions};
+use crate::io::plan::{lazyframe_plans,lazyframe_profile};
+use crate::io::render::{lazyframe_as_rendered,RenderAs,RenderOptions};
+use crate::io::schema::lazyframe_schema;
+use crate::io::stream::ChannelWriter;
+use crate::filters::sample::{sample,SampleRawParameters};
+use crate::services::query::{apply_query,paginate,FeedQuery,PageQuery};
+
+
+#[derive(Debug, Deserialize)]
+pub struct DisplayQuery {
+    pub sample: Option<u64>,
+    pub seed: Option<u64>,
+    pub format: Option<String>,
+    pub orient: Option<String>,
+    pub compression: Option<String>,
+    pub compression_level: Option<i32>,
+
+    // Table rendering
+    pub r#as: Option<String>,
+    pub max_rows: Option<usize>,
+    pub max_cols: Option<usize>,
+    pub max_str_len: Option<usize>,
+    pub float_precision: Option<usize>,
+    pub thousands_separator: Option<String>
+}
+
+
+#[derive(Debug, Deserialize)]
+pub struct DownloadQuery {
+    pub format: Option<String>,
+    pub orient: Option<String>,
+    pub compression: Option<String>,
+    pub compression_level: Option<i32>
+}
+
+
+/// Picks the output format from `?format=` first, then from the `Accept`
+/// header: the known media type with the highest q-value wins, `*/*` and
+/// `type/*` standing for `default`. Without `Accept` the format is `default`.
+/// An unknown `?format=` is answered with 400, and an `Accept` header that
+/// rules out every format with 406.
+fn output_format(
+    req: &HttpRequest,
+    format: &Option<String>,
+    default: OutputFormat
+) -> Result<OutputFormat, HttpResponse> {
+    if let Some(format) = format {
+        return OutputFormat::from_name(format).ok_or(
+            HttpResponse::BadRequest()
+                .content_type(ContentType::plaintext())
+                .body(format!("Unknown format {format}"))
+        )
+    }
+
+    let accept = match req.headers().get("Accept").and_then(|accept| accept.to_str().ok()) {
+        Some(accept) if !accept.trim().is_empty() => accept,
+        _ => return Ok(default)
+    };
+
+    let default_type = default.content_type().split(';').next().unwrap_or("");
+    let mut accepted: Vec<(OutputFormat, f32)> = Vec::new();
+    let mut wildcard: Option<f32> = None;
+    for media_range in accept.split(',') {
+        let mut parts = media_range.split(';');
+        let media_type = parts.next().unwrap_or("").trim().to_lowercase();
+        let quality = match parts.find_map(|parameter| parameter.trim().strip_prefix("q=")) {
+            Some(quality) => match quality.trim().parse::<f32>() {
+                Ok(quality) => quality,
+                Err(_) => continue
+            },
+            None => 1.0
+        };
+
+        if let Some(format) = OutputFormat::from_mime(&media_type) {
+            accepted.push((format, quality));
+        } else if media_type == "*/*"
+            || media_type.strip_suffix("/*").is_some_and(|range| default_type.starts_with(&format!("{range}/"))) {
+            wildcard = Some(wildcard.unwrap_or(0.0).max(quality));
+        }
+    }
+
+    // A media type listed explicitly takes precedence over wildcards
+    if let Some(quality) = wildcard && !accepted.iter().any(|(format, _)| *format == default) {
+        accepted.push((default, quality));
+    }
+
+    // Highest q-value first, the order of the header breaking ties
+    let mut best: Option<(OutputFormat, f32)> = None;
+    for (format, quality) in accepted {
+        if quality > 0.0 && best.is_none_or(|(_, best)| quality > best) {
+            best = Some((format, quality));
+        }
+    }
+
+    best.map(|(format, _)| format).ok_or(
+        HttpResponse::NotAcceptable()
+            .content_type(ContentType::plaintext())
+            .body(format!("None of the media types accepted ({accept}) is available"))
+    )
+}
 
 
 #[get("/feeds/{feed_name}/display")]
-pub async fn display_feed(path: web::Path<String>) -> impl Responder {
+pub async fn display_feed(
+    req: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<DisplayQuery>,
+    feed_query: web::Query<FeedQuery>
+) -> impl Responder {
     const LOG_HEADER: &str = "services::display_feed";
 
     // Path variables
     let feed_name = path.into_inner();
 
+    // Output format
+    let options = OutputOptions {
+        orient: query.orient.clone(),
+        compression: query.compression.clone(),
+        compression_level: query.compression_level
+    };
+    let format = match output_format(&req, &query.format, OutputFormat::Table) {
+        Ok(format) => format,
+        Err(response) => return response
+    };
+    if let Err(err) = options.check(format) {
+        return HttpResponse::BadRequest()
+            .content_type(ContentType::plaintext())
+            .body(format!("{err}"))
+    }
+
+    // Table rendering
+    let render_as = match (&query.r#as, format) {
+        (None, _) => RenderAs::Text,
+        (Some(render_as), OutputFormat::Table) => match RenderAs::from_name(render_as) {
+            Some(render_as) => render_as,
+            None => {
+                return HttpResponse::BadRequest()
+                    .content_type(ContentType::plaintext())
+                    .body(format!("Unknown renderer {render_as}, expected text, markdown or html"))
+            }
+        },
+        (Some(_), _) => {
+            return HttpResponse::BadRequest()
+                .content_type(ContentType::plaintext())
+                .body("as is only supported with the table format")
+        }
+    };
+    let render_options = RenderOptions {
+        max_rows: query.max_rows,
+        max_cols: query.max_cols,
+        max_str_len: query.max_str_len,
+        float_precision: query.float_precision,
+        thousands_separator: query.thousands_separator.clone()
+    };
+    if let Err(err) = render_options.check() {
+        return HttpResponse::BadRequest()
+            .content_type(ContentType::plaintext())
+            .body(format!("{err}"))
+    }
+
     // Init timer
     let timer = Instant::now();
 
@@ -24,9 +179,44 @@ pub async fn display_feed(path: web::Path<String>) -> impl Responder {
 
     match get_feed(&feed_name) {
         // Feed has been properly defined as a LazyFrame
-        Ok(lf) => {
+        Ok(mut lf) => {
             logger_elapsed!(timer, "{LOG_HEADER} Fetched feed {feed_name}");
 
+            // Projection, predicate, ordering and slicing from the query string
+            match apply_query(lf, &feed_query) {
+                Ok(queried_lf) => {
+                    lf = queried_lf;
+                },
+                Err(err) => {
+                    logger_elapsed!(timer, "{LOG_HEADER} Error while querying feed {feed_name}: {err}");
+                    return HttpResponse::BadRequest()
+                        .content_type(ContentType::plaintext())
+                        .body(format!("Error while querying feed {feed_name}: {err}"))
+                }
+            }
+
+            // Sampled preview
+            if let Some(n) = query.sample {
+                let parameters = SampleRawParameters {
+                    n: Some(n),
+                    fraction: None,
+                    seed: query.seed,
+                    by: None,
+                    with_replacement: None
+                };
+                match sample(lf, &parameters) {
+                    Ok(sampled_lf) => {
+                        lf = sampled_lf;
+                    },
+                    Err(err) => {
+                        logger_elapsed!(timer, "{LOG_HEADER} Error while sampling feed {feed_name}: {err}");
+                        return HttpResponse::BadRequest()
+                            .content_type(ContentType::plaintext())
+                            .body(format!("Error while sampling feed {feed_name}: {err}"))
+                    }
+                }
+            }
+
             match lf.explain(true) {
                 Ok(lf_explained) => {
                     logger_elapsed!(timer, "{LOG_HEADER} {lf_explained}");
@@ -40,12 +230,33 @@ pub async fn display_feed(path: web::Path<String>) -> impl Responder {
             }
 
             // Convert LazyFrame to DataFrame and display it
-            match lazyframe_as_str(lf) {
+            if format == OutputFormat::Table {
+                return match lazyframe_as_rendered(lf, render_as, &render_options) {
+                    Ok(result) => {
+                        logger_elapsed!(timer, "{LOG_HEADER} Collected feed {feed_name}");
+                        HttpResponse::Ok()
+                            .content_type(render_as.content_type())
+                            .body(result)
+                    },
+                    Err(err) => {
+                        logger_elapsed!(timer, "{LOG_HEADER} Error while collecting feed {feed_name}: {err}");
+                        HttpResponse::InternalServerError()
+                            .content_type(ContentType::plaintext())
+                            .body(format!("Error while collecting feed {feed_name}: {err}"))
+                    }
+                }
+            }
+
+            match lazyframe_as_format(lf, format, &options) {
                 Ok(result) => {
                     logger_elapsed!(timer, "{LOG_HEADER} Collected feed {feed_name}");
-                    HttpResponse::Ok()
-                        .content_type(ContentType::plaintext())
-                        .body(result)
+                    let mut response = HttpResponse::Ok();
+                    response.content_type(format.content_type());
+                    if !format.is_text() {
+                        // Binary formats are compressed by their writer
+                        response.insert_header(ContentEncoding::Identity);
+                    }
+                    response.body(result)
                 },
                 Err(err) => {
                     logger_elapsed!(timer, "{LOG_HEADER} Error while collecting feed {feed_name}: {err}");
@@ -120,12 +331,34 @@ pub async fn new_temp_feed(params: Json<FeedParameters>) -> impl Responder {
 
 
 #[get("/feeds/{feed_name}")]
-pub async fn download_feed(path: web::Path<String>) -> impl Responder {
+pub async fn download_feed(
+    req: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<DownloadQuery>,
+    feed_query: web::Query<FeedQuery>
+) -> impl Responder {
     const LOG_HEADER: &str = "services::download_feed";
 
     // Path variables
     let feed_name = path.into_inner();
 
+    // Output format
+    let options = OutputOptions {
+        orient: query.orient.clone(),
+        compression: query.compression.clone(),
+        compression_level: query.compression_level
+    };
+    let format = match output_format(&req, &query.format, OutputFormat::ArrowStream) {
+        Ok(format) => format,
+        Err(response) => return response
+    };
+    if let Err(err) = options.check(format) {
+        return HttpResponse::BadRequest()
+            .content_type(ContentType::plaintext())
+            .body(format!("{err}"))
+    }
+    let filename = format!("{}.{}", feed_name.replace(['"', '/', '\\'], "_"), format.extension());
+
     // Init timer
     let timer = Instant::now();
 
@@ -133,9 +366,22 @@ pub async fn download_feed(path: web::Path<String>) -> impl Responder {
 
     match get_feed(&feed_name) {
         // Feed has been properly defined as a LazyFrame
-        Ok(lf) => {
+        Ok(mut lf) => {
             logger_elapsed!(timer, "{LOG_HEADER} Fetched feed {feed_name}");
 
+            // Projection, predicate, ordering and slicing from the query string
+            match apply_query(lf, &feed_query) {
+                Ok(queried_lf) => {
+                    lf = queried_lf;
+                },
+                Err(err) => {
+                    logger_elapsed!(timer, "{LOG_HEADER} Error while querying feed {feed_name}: {err}");
+                    return HttpResponse::BadRequest()
+                        .content_type(ContentType::plaintext())
+                        .body(format!("Error while querying feed {feed_name}: {err}"))
+                }
+            }
+
             match lf.explain(true) {
                 Ok(lf_explained) => {
                     logger_elapsed!(timer, "{LOG_HEADER} {lf_explained}");
@@ -148,14 +394,55 @@ pub async fn download_feed(path: web::Path<String>) -> impl Responder {
                 }
             }
 
+            // Stream record batches as they are produced
+            if format == OutputFormat::ArrowStream {
+                let (sender, receiver) = tokio::sync::mpsc::channel::<Result<Bytes, std::io::Error>>(4);
+                let streamed_feed_name = feed_name.clone();
+                let compression = match options.ipc_compression() {
+                    Ok(compression) => compression,
+                    Err(err) => {
+                        return HttpResponse::BadRequest()
+                            .content_type(ContentType::plaintext())
+                            .body(format!("{err}"))
+                    }
+                };
+
+                actix_web::rt::task::spawn_blocking(move || {
+                    match lazyframe_as_arrowstream(lf, ChannelWriter::new(sender.clone()), compression) {
+                        Ok(()) => {
+                            logger_elapsed!(timer, "{LOG_HEADER} Streamed feed {streamed_feed_name}");
+                        },
+                        Err(err) => {
+                            logger_elapsed!(timer, "{LOG_HEADER} Error while streaming feed {streamed_feed_name}: {err}");
+                            let _ = sender.blocking_send(Err(std::io::Error::other(err.to_string())));
+                        }
+                    }
+                });
+
+                let body = stream::unfold(receiver, |mut receiver| async move {
+                    receiver.recv().await.map(|chunk| (chunk, receiver))
+                });
+
+                return HttpResponse::Ok()
+                    .content_type(format.content_type())
+                    .insert_header(ContentEncoding::Identity)
+                    .append_header(("Content-Disposition", format!("attachment; filename=\"{filename}\"")))
+                    .streaming(body)
+            }
+
             // Convert LazyFrame to DataFrame and display it
-            match lazyframe_as_arrowbytes(lf) {
-                Ok(arrow_bytes) => {
+            match lazyframe_as_format(lf, format, &options) {
+                Ok(bytes) => {
                     logger_elapsed!(timer, "{LOG_HEADER} Collected feed {feed_name}");
-                    HttpResponse::Ok()
-                        .content_type("application/vnd.apache.arrow.file")
-                        .append_header(("Content-Disposition", "attachment; filename=\"data.arrow\""))
-                        .body(arrow_bytes)
+                    let mut response = HttpResponse::Ok();
+                    response
+                        .content_type(format.content_type())
+                        .append_header(("Content-Disposition", format!("attachment; filename=\"{filename}\"")));
+                    if !format.is_text() {
+                        // Binary formats are compressed by their writer
+                        response.insert_header(ContentEncoding::Identity);
+                    }
+                    response.body(bytes)
                 },
                 Err(err) => {
                     logger_elapsed!(timer, "{LOG_HEADER} Error while collecting feed {feed_name}: {err}");
@@ -173,3 +460,314 @@ pub async fn download_feed(path: web::Path<String>) -> impl Responder {
         }
     }
 }
+
+
+/// Pages through a feed ordered by `order_by`. JSON pages are wrapped as
+/// `{"data": ..., "next_cursor": ..., "count": ...}`; other formats carry the
+/// cursor and count in the `X-Next-Cursor` and `X-Total-Count` headers.
+#[get("/feeds/{feed_name}/pages")]
+pub async fn page_feed(
+    req: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<DownloadQuery>,
+    feed_query: web::Query<FeedQuery>,
+    page_query: web::Query<PageQuery>
+) -> impl Responder {
+    const LOG_HEADER: &str = "services::page_feed";
+
+    // Path variables
+    let feed_name = path.into_inner();
+
+    // Output format
+    let options = OutputOptions {
+        orient: query.orient.clone(),
+        compression: query.compression.clone(),
+        compression_level: query.compression_level
+    };
+    let format = match output_format(&req, &query.format, OutputFormat::Json) {
+        Ok(format) => format,
+        Err(response) => return response
+    };
+    if let Err(err) = options.check(format) {
+        return HttpResponse::BadRequest()
+            .content_type(ContentType::plaintext())
+            .body(format!("{err}"))
+    }
+
+    // Init timer
+    let timer = Instant::now();
+
+    logger_elapsed!(timer, "{LOG_HEADER} Fetching feed {feed_name}");
+
+    match get_feed(&feed_name) {
+        // Feed has been properly defined as a LazyFrame
+        Ok(lf) => {
+            logger_elapsed!(timer, "{LOG_HEADER} Fetched feed {feed_name}");
+
+            let page = match paginate(lf, &feed_query, &page_query) {
+                Ok(page) => page,
+                Err(err) => {
+                    logger_elapsed!(timer, "{LOG_HEADER} Error while paginating feed {feed_name}: {err}");
+                    return HttpResponse::BadRequest()
+                        .content_type(ContentType::plaintext())
+                        .body(format!("Error while paginating feed {feed_name}: {err}"))
+                }
+            };
+
+            logger_elapsed!(timer, "{LOG_HEADER} Collected page of feed {feed_name}");
+
+            match lazyframe_as_format(page.df.lazy(), format, &options) {
+                Ok(bytes) => {
+                    let mut response = HttpResponse::Ok();
+                    response.content_type(format.content_type());
+
+                    if format == OutputFormat::Json {
+                        let mut body: Vec<u8> = Vec::with_capacity(bytes.len() + 128);
+                        body.extend_from_slice(b"{\"data\":");
+                        body.extend_from_slice(&bytes);
+                        body.extend_from_slice(b",\"next_cursor\":");
+                        body.extend_from_slice(serde_json::Value::from(page.next_cursor).to_string().as_bytes());
+                        if let Some(count) = page.count {
+                            body.extend_from_slice(format!(",\"count\":{count}").as_bytes());
+                        }
+                        body.push(b'}');
+                        return response.body(body)
+                    }
+
+                    if let Some(next_cursor) = page.next_cursor {
+                        response.insert_header(("X-Next-Cursor", next_cursor));
+                    }
+                    if let Some(count) = page.count {
+                        response.insert_header(("X-Total-Count", count.to_string()));
+                    }
+                    if !format.is_text() {
+                        // Binary formats are compressed by their writer
+                        response.insert_header(ContentEncoding::Identity);
+                    }
+                    response.body(bytes)
+                },
+                Err(err) => {
+                    logger_elapsed!(timer, "{LOG_HEADER} Error while writing page of feed {feed_name}: {err}");
+                    HttpResponse::InternalServerError()
+                        .content_type(ContentType::plaintext())
+                        .body(format!("Error while writing page of feed {feed_name}: {err}"))
+                }
+            }
+        },
+        Err(err) => {
+            logger_elapsed!(timer, "{LOG_HEADER} Error while fetching feed {feed_name}: {err}");
+            HttpResponse::InternalServerError()
+                .content_type(ContentType::plaintext())
+                .body(format!("Error while fetching feed {feed_name}: {err}"))
+        }
+    }
+}
+
+
+/// Answers with the output schema of `lf` as
+/// `{"feed_name": ..., "columns": [{"name", "dtype", "arrow_dtype", "nullable"}]}`.
+fn schema_response(
+    feed_name: &str,
+    lf: LazyFrame
+) -> HttpResponse {
+    match lazyframe_schema(lf) {
+        Ok(columns) => {
+            let mut body: Map<String, Value> = Map::with_capacity(2);
+            body.insert("feed_name".into(), Value::from(feed_name));
+            body.insert("columns".into(), serde_json::to_value(columns).unwrap_or_default());
+            HttpResponse::Ok().json(body)
+        },
+        Err(err) => {
+            HttpResponse::BadRequest()
+                .content_type(ContentType::plaintext())
+                .body(format!("Error while resolving schema of feed {feed_name}: {err}"))
+        }
+    }
+}
+
+
+#[get("/feeds/{feed_name}/schema")]
+pub async fn feed_schema(path: web::Path<String>) -> impl Responder {
+    const LOG_HEADER: &str = "services::feed_schema";
+
+    // Path variables
+    let feed_name = path.into_inner();
+
+    // Init timer
+    let timer = Instant::now();
+
+    logger_elapsed!(timer, "{LOG_HEADER} Fetching feed {feed_name}");
+
+    match get_feed(&feed_name) {
+        // Feed has been properly defined as a LazyFrame
+        Ok(lf) => {
+            logger_elapsed!(timer, "{LOG_HEADER} Fetched feed {feed_name}");
+            let response = schema_response(&feed_name, lf);
+            logger_elapsed!(timer, "{LOG_HEADER} Resolved schema of feed {feed_name}");
+            response
+        },
+        Err(err) => {
+            logger_elapsed!(timer, "{LOG_HEADER} Error while fetching feed {feed_name}: {err}");
+            HttpResponse::InternalServerError()
+                .content_type(ContentType::plaintext())
+                .body(format!("Error while fetching feed {feed_name}: {err}"))
+        }
+    }
+}
+
+
+#[post("/feeds/schema")]
+pub async fn temp_feed_schema(params: Json<FeedParameters>) -> impl Responder {
+    const LOG_HEADER: &str = "services::temp_feed_schema";
+
+    // Init timer
+    let timer = Instant::now();
+
+    let feed_name = params.feed_name.clone();
+
+    logger_elapsed!(timer, "{LOG_HEADER} Fetching feed {feed_name}");
+
+    match new_temporary_feed(&feed_name, &params) {
+        // Feed has been properly defined as a LazyFrame
+        Ok(lf) => {
+            logger_elapsed!(timer, "{LOG_HEADER} Fetched feed {feed_name}");
+            let response = schema_response(&feed_name, lf);
+            logger_elapsed!(timer, "{LOG_HEADER} Resolved schema of feed {feed_name}");
+            response
+        },
+        Err(err) => {
+            logger_elapsed!(timer, "{LOG_HEADER} Error while fetching feed {feed_name}: {err}");
+            HttpResponse::InternalServerError()
+                .content_type(ContentType::plaintext())
+                .body(format!("Error while fetching feed {feed_name}: {err}"))
+        }
+    }
+}
+
+
+/// Returns the unoptimized and optimized plans of the feed, with the query
+/// string applied, as text and DOT graphs, without executing it.
+#[get("/feeds/{feed_name}/explain")]
+pub async fn explain_feed(path: web::Path<String>, feed_query: web::Query<FeedQuery>) -> impl Responder {
+    const LOG_HEADER: &str = "services::explain_feed";
+
+    // Path variables
+    let feed_name = path.into_inner();
+
+    // Init timer
+    let timer = Instant::now();
+
+    logger_elapsed!(timer, "{LOG_HEADER} Fetching feed {feed_name}");
+
+    match get_feed(&feed_name).and_then(|lf| apply_query(lf, &feed_query)) {
+        // Feed has been properly defined as a LazyFrame
+        Ok(lf) => {
+            logger_elapsed!(timer, "{LOG_HEADER} Fetched feed {feed_name}");
+
+            match lazyframe_plans(&lf) {
+                Ok(plans) => {
+                    logger_elapsed!(timer, "{LOG_HEADER} Explained feed {feed_name}");
+                    HttpResponse::Ok().json(plans)
+                },
+                Err(err) => {
+                    logger_elapsed!(timer, "{LOG_HEADER} Error while explaining feed {feed_name}: {err}");
+                    HttpResponse::InternalServerError()
+                        .content_type(ContentType::plaintext())
+                        .body(format!("Error while explaining feed {feed_name}: {err}"))
+                }
+            }
+        },
+        Err(err) => {
+            logger_elapsed!(timer, "{LOG_HEADER} Error while fetching feed {feed_name}: {err}");
+            HttpResponse::InternalServerError()
+                .content_type(ContentType::plaintext())
+                .body(format!("Error while fetching feed {feed_name}: {err}"))
+        }
+    }
+}
+
+
+/// Runs the feed, with the query string applied, and returns the start and
+/// end of every executed node in microseconds.
+#[get("/feeds/{feed_name}/profile")]
+pub async fn profile_feed(path: web::Path<String>, feed_query: web::Query<FeedQuery>) -> impl Responder {
+    const LOG_HEADER: &str = "services::profile_feed";
+
+    // Path variables
+    let feed_name = path.into_inner();
+
+    // Init timer
+    let timer = Instant::now();
+
+    logger_elapsed!(timer, "{LOG_HEADER} Fetching feed {feed_name}");
+
+    match get_feed(&feed_name).and_then(|lf| apply_query(lf, &feed_query)) {
+        // Feed has been properly defined as a LazyFrame
+        Ok(lf) => {
+            logger_elapsed!(timer, "{LOG_HEADER} Fetched feed {feed_name}");
+
+            match lazyframe_profile(lf) {
+                Ok(profile) => {
+                    logger_elapsed!(timer, "{LOG_HEADER} Profiled feed {feed_name}");
+                    HttpResponse::Ok().json(profile)
+                },
+                Err(err) => {
+                    logger_elapsed!(timer, "{LOG_HEADER} Error while profiling feed {feed_name}: {err}");
+                    HttpResponse::InternalServerError()
+                        .content_type(ContentType::plaintext())
+                        .body(format!("Error while profiling feed {feed_name}: {err}"))
+                }
+            }
+        },
+        Err(err) => {
+            logger_elapsed!(timer, "{LOG_HEADER} Error while fetching feed {feed_name}: {err}");
+            HttpResponse::InternalServerError()
+                .content_type(ContentType::plaintext())
+                .body(format!("Error while fetching feed {feed_name}: {err}"))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use actix_web::http::StatusCode;
+    use actix_web::test::TestRequest;
+
+    use super::*;
+
+    fn negotiate(accept: Option<&str>, format: Option<&str>) -> Result<OutputFormat, StatusCode> {
+        let mut request = TestRequest::default();
+        if let Some(accept) = accept {
+            request = request.insert_header(("Accept", accept));
+        }
+        output_format(&request.to_http_request(), &format.map(str::to_string), OutputFormat::ArrowStream)
+            .map_err(|response| response.status())
+    }
+
+    #[test]
+    fn output_format_prefers_highest_quality() {
+        assert_eq!(negotiate(Some("text/csv;q=0.1, application/json"), None), Ok(OutputFormat::Json));
+        assert_eq!(negotiate(Some("application/json;q=0.5, text/csv;q=0.9"), None), Ok(OutputFormat::Csv));
+        assert_eq!(negotiate(Some("text/csv, application/json"), None), Ok(OutputFormat::Csv));
+        assert_eq!(negotiate(Some("text/csv;q=0.5, */*"), None), Ok(OutputFormat::ArrowStream));
+        assert_eq!(negotiate(Some("text/html, */*;q=0.8"), None), Ok(OutputFormat::ArrowStream));
+    }
+
+    #[test]
+    fn output_format_falls_back_to_default() {
+        assert_eq!(negotiate(None, None), Ok(OutputFormat::ArrowStream));
+        assert_eq!(negotiate(Some("*/*"), None), Ok(OutputFormat::ArrowStream));
+        assert_eq!(negotiate(Some("application/*"), None), Ok(OutputFormat::ArrowStream));
+        assert_eq!(negotiate(Some("text/csv"), Some("json")), Ok(OutputFormat::Json));
+    }
+
+    #[test]
+    fn output_format_rejects_unacceptable_formats() {
+        assert_eq!(negotiate(Some("image/png"), None), Err(StatusCode::NOT_ACCEPTABLE));
+        assert_eq!(negotiate(Some("text/csv;q=0"), None), Err(StatusCode::NOT_ACCEPTABLE));
+        assert_eq!(negotiate(Some("application/vnd.apache.arrow.stream;q=0, */*"), None), Err(StatusCode::NOT_ACCEPTABLE));
+        assert_eq!(negotiate(Some("text/*"), None), Err(StatusCode::NOT_ACCEPTABLE));
+        assert_eq!(negotiate(None, Some("xml")), Err(StatusCode::BAD_REQUEST));
+    }
+}
@@ -0,0 +1,590 @@
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use polars::{error::ErrString, prelude::*};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+
+/// Projection, predicate, ordering and slicing requested through the query
+/// string of the GET endpoints, applied on top of the feed's `LazyFrame`.
+#[derive(Debug, Deserialize)]
+pub struct FeedQuery {
+    pub columns: Option<String>,
+    pub r#where: Option<String>,
+    pub sort: Option<String>,
+    pub limit: Option<u64>,
+    pub offset: Option<u64>
+}
+
+
+/// Keyset pagination over `order_by`, resumed after the position encoded in
+/// `cursor`.
+#[derive(Debug, Deserialize)]
+pub struct PageQuery {
+    pub order_by: Option<String>,
+    pub page_size: Option<u64>,
+    pub cursor: Option<String>,
+    pub count: Option<bool>
+}
+
+
+/// Decoded content of a `next_cursor` token: the page ordering and the key of
+/// the last row returned.
+#[derive(Debug, Serialize, Deserialize)]
+struct Cursor {
+    order_by: Vec<String>,
+    descending: Vec<bool>,
+    after: Vec<Value>
+}
+
+
+/// One page of a feed, with the cursor of the following page if any and the
+/// total row count when requested.
+pub struct Page {
+    pub df: DataFrame,
+    pub next_cursor: Option<String>,
+    pub count: Option<u64>
+}
+
+
+const OPERATORS: [&str; 7] = [">=", "<=", "!=", "==", ">", "<", "="];
+
+/// Position of the row in the feed, breaking ties between equal `order_by`
+/// keys of paginated rows.
+const ROW_INDEX: &str = "_row_index";
+
+
+/// Splits `value` on commas that are not inside single or double quotes.
+fn split_unquoted(value: &str) -> Vec<&str> {
+    let mut parts: Vec<&str> = Vec::new();
+    let mut quote: Option<char> = None;
+    let mut start = 0;
+    for (i, c) in value.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), _) if q == c => quote = None,
+            (None, ',') => {
+                parts.push(&value[start..i]);
+                start = i + 1;
+            },
+            _ => {}
+        }
+    }
+    parts.push(&value[start..]);
+
+    parts.into_iter().map(str::trim).filter(|part| !part.is_empty()).collect()
+}
+
+
+/// Reads `value` as a literal of the column's `dtype`: numbers and booleans are
+/// parsed, strings are taken as is and other types are cast from the string.
+fn literal(value: &str, dtype: &DataType) -> Result<Expr, Box<dyn std::error::Error>> {
+    if dtype.is_integer() && let Ok(value) = value.parse::<i64>() {
+        return Ok(lit(value))
+    }
+    if dtype.is_numeric() {
+        return match value.parse::<f64>() {
+            Ok(value) => Ok(lit(value)),
+            Err(_) => Err(format!("Invalid number {value}").into())
+        }
+    }
+
+    match dtype {
+        DataType::Boolean => match value {
+            "true" => Ok(lit(true)),
+            "false" => Ok(lit(false)),
+            _ => Err(format!("Invalid boolean {value}").into())
+        },
+        DataType::String => Ok(lit(value)),
+        _ => Ok(lit(value).cast(dtype.clone()))
+    }
+}
+
+
+/// Compiles one `column<op>value` condition, `op` being one of `>=`, `<=`,
+/// `!=`, `==` (or `=`), `>` and `<`. A `null` value compiles to a null check.
+fn condition(condition: &str, schema: &Schema) -> Result<Expr, Box<dyn std::error::Error>> {
+    let (position, operator) = OPERATORS
+        .iter()
+        .filter_map(|operator| condition.find(operator).map(|position| (position, *operator)))
+        .min_by_key(|(position, operator)| (*position, usize::MAX - operator.len()))
+        .ok_or(format!("Invalid condition {condition}, expected column<op>value"))?;
+
+    let column = condition[..position].trim();
+    let value = condition[position + operator.len()..].trim();
+    let value = value
+        .strip_prefix('"').and_then(|v| v.strip_suffix('"'))
+        .or(value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+        .unwrap_or(value);
+
+    let dtype = schema.get(column).ok_or(format!("Unknown column {column}"))?;
+
+    if value == "null" {
+        return match operator {
+            "==" | "=" => Ok(col(column).is_null()),
+            "!=" => Ok(col(column).is_not_null()),
+            _ => Err(format!("Invalid condition {condition}, null only compares with == or !=").into())
+        }
+    }
+
+    let value = literal(value, dtype)?;
+    Ok(match operator {
+        ">=" => col(column).gt_eq(value),
+        "<=" => col(column).lt_eq(value),
+        "!=" => col(column).neq(value),
+        ">" => col(column).gt(value),
+        "<" => col(column).lt(value),
+        _ => col(column).eq(value)
+    })
+}
+
+
+/// Compiles `where` into a predicate, conditions separated by commas being
+/// combined with a logical and.
+pub fn predicate(r#where: &str, schema: &Schema) -> Result<Expr, Box<dyn std::error::Error>> {
+    let mut predicate: Option<Expr> = None;
+    for part in split_unquoted(r#where) {
+        let expr = condition(part, schema)?;
+        predicate = Some(match predicate {
+            Some(predicate) => predicate.and(expr),
+            None => expr
+        });
+    }
+
+    predicate.ok_or(Box::new(PolarsError::InvalidOperation(ErrString::new_static("Empty where clause"))))
+}
+
+
+/// Splits a `sort`/`order_by` value into columns and descending flags, a
+/// leading `-` sorting descending.
+fn sort_keys(sort: &str, schema: &Schema) -> Result<(Vec<String>, Vec<bool>), Box<dyn std::error::Error>> {
+    let mut by: Vec<String> = Vec::new();
+    let mut descending: Vec<bool> = Vec::new();
+    for key in split_unquoted(sort) {
+        let (column, desc) = match key.strip_prefix('-') {
+            Some(column) => (column, true),
+            None => (key.strip_prefix('+').unwrap_or(key), false)
+        };
+        if !schema.contains(column) {
+            return Err(format!("Unknown column {column}").into())
+        }
+        by.push(column.to_string());
+        descending.push(desc);
+    }
+
+    Ok((by, descending))
+}
+
+
+/// Selects `columns`, checking they all exist.
+fn project(
+    lf: LazyFrame,
+    columns: &str,
+    schema: &Schema
+) -> Result<LazyFrame, Box<dyn std::error::Error>> {
+    let columns: Vec<&str> = split_unquoted(columns);
+    if let Some(column) = columns.iter().find(|column| !schema.contains(column)) {
+        return Err(format!("Unknown column {column}").into())
+    }
+
+    Ok(lf.select(columns.into_iter().map(col).collect::<Vec<Expr>>()))
+}
+
+
+/// Applies `where`, `sort` (a leading `-` sorts descending), `offset`/`limit`
+/// and finally `columns`, so the predicate and ordering may use columns that
+/// are not returned. Everything stays lazy for the optimizer to push the
+/// projection and predicate down to the scan.
+pub fn apply_query(
+    mut lf: LazyFrame,
+    query: &FeedQuery
+) -> Result<LazyFrame, Box<dyn std::error::Error>> {
+    let schema = lf.collect_schema()?;
+
+    if let Some(r#where) = &query.r#where {
+        lf = lf.filter(predicate(r#where, &schema)?);
+    }
+
+    if let Some(sort) = &query.sort {
+        let (by, descending) = sort_keys(sort, &schema)?;
+        lf = lf.sort(
+            by,
+            SortMultipleOptions::default()
+                .with_order_descending_multi(descending)
+                .with_maintain_order(true)
+        );
+    }
+
+    match (query.offset, query.limit) {
+        (Some(offset), limit) => {
+            lf = lf.slice(offset as i64, limit.unwrap_or(u64::MAX).min(IdxSize::MAX as u64) as IdxSize);
+        },
+        (None, Some(limit)) => {
+            lf = lf.limit(limit.min(IdxSize::MAX as u64) as IdxSize);
+        },
+        (None, None) => {}
+    }
+
+    if let Some(columns) = &query.columns {
+        lf = project(lf, columns, &schema)?;
+    }
+
+    Ok(lf)
+}
+
+
+/// Checks `dtype` round trips through a cursor: booleans, strings, numbers
+/// and temporal types, the latter stored as their physical integer.
+/// Categorical keys are rejected since their physical codes do not order as
+/// their values.
+fn check_cursor_key(key: &str, dtype: &DataType) -> Result<(), Box<dyn std::error::Error>> {
+    match dtype {
+        DataType::Boolean | DataType::String => Ok(()),
+        dtype if dtype.is_float() || dtype.is_integer() => Ok(()),
+        DataType::Date | DataType::Datetime(_, _) | DataType::Duration(_) | DataType::Time => Ok(()),
+        _ => Err(format!("Column {key} of type {dtype} cannot be used as order_by key").into())
+    }
+}
+
+
+/// Reads the key of the last row as JSON values, temporal keys being stored
+/// as their physical integer.
+fn cursor_values(
+    df: &DataFrame,
+    order_by: &[String],
+    row: usize
+) -> Result<Vec<Value>, Box<dyn std::error::Error>> {
+    let mut values: Vec<Value> = Vec::with_capacity(order_by.len());
+    for key in order_by.iter() {
+        let column = df.column(key)?.to_physical_repr();
+        let value = match column.get(row)? {
+            AnyValue::Null => {
+                return Err(format!("Column {key} contains nulls and cannot be used as order_by key").into())
+            },
+            AnyValue::Boolean(value) => Value::from(value),
+            AnyValue::String(value) => Value::from(value),
+            AnyValue::StringOwned(value) => Value::from(value.as_str()),
+            value if column.dtype().is_float() => match value.extract::<f64>() {
+                Some(value) if value.is_finite() => Value::from(value),
+                _ => {
+                    return Err(format!("Column {key} contains NaN or infinite values and cannot be used as order_by key").into())
+                }
+            },
+            value if column.dtype().is_unsigned_integer() => Value::from(value.extract::<u64>()),
+            value if column.dtype().is_integer() => Value::from(value.extract::<i64>()),
+            _ => {
+                return Err(format!("Column {key} has an unsupported type for order_by").into())
+            }
+        };
+        values.push(value);
+    }
+
+    Ok(values)
+}
+
+
+/// Turns a cursor value back into a literal of the key's `dtype`.
+fn cursor_literal(value: &Value, dtype: &DataType) -> Result<Expr, Box<dyn std::error::Error>> {
+    let literal = match dtype {
+        DataType::Boolean => value.as_bool().map(lit),
+        DataType::String => value.as_str().map(lit),
+        dtype if dtype.is_float() => value.as_f64().map(lit),
+        dtype if dtype.is_unsigned_integer() => value.as_u64().map(lit),
+        dtype if dtype.is_integer() => value.as_i64().map(lit),
+        dtype if dtype.is_temporal() => value.as_i64().map(|value| lit(value).cast(dtype.clone())),
+        _ => None
+    };
+
+    literal.ok_or(format!("Invalid cursor value {value} for type {dtype}").into())
+}
+
+
+fn encode_cursor(cursor: &Cursor) -> Result<String, Box<dyn std::error::Error>> {
+    Ok(URL_SAFE_NO_PAD.encode(serde_json::to_vec(cursor)?))
+}
+
+
+fn decode_cursor(token: &str) -> Result<Cursor, Box<dyn std::error::Error>> {
+    let bytes = URL_SAFE_NO_PAD.decode(token).map_err(|_| "Invalid cursor")?;
+    serde_json::from_slice(&bytes).map_err(|_| "Invalid cursor".into())
+}
+
+
+/// Returns one page of `page_size` rows ordered by `order_by`, after the row
+/// encoded in `cursor`.
+///
+/// Later pages are a predicate on the key of the last row returned rather
+/// than an offset, so they cost the same whatever their depth. The key is
+/// `order_by` followed by the position of the row in the feed, so rows with
+/// equal `order_by` values are neither skipped nor repeated across pages. A
+/// null or NaN key at a page boundary is rejected. `where` and `columns` of
+/// `query` apply as on the other GET endpoints; `sort`, `limit` and `offset`
+/// are replaced by the pagination. With `count`, the total number of rows
+/// matching `where` is computed as well.
+pub fn paginate(
+    mut lf: LazyFrame,
+    query: &FeedQuery,
+    page_query: &PageQuery
+) -> Result<Page, Box<dyn std::error::Error>> {
+    if query.sort.is_some() || query.limit.is_some() || query.offset.is_some() {
+        return Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("sort, limit and offset cannot be combined with pagination, use order_by and page_size"))))
+    }
+
+    let schema = lf.collect_schema()?;
+
+    let order_by = page_query
+        .order_by
+        .as_deref()
+        .ok_or(PolarsError::InvalidOperation(ErrString::new_static("order_by is expected for pagination")))?;
+    let (by, descending) = sort_keys(order_by, &schema)?;
+    if by.is_empty() {
+        return Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("order_by is expected for pagination"))))
+    }
+    for key in by.iter() {
+        check_cursor_key(key, schema.try_get(key)?)?;
+    }
+    if schema.contains(ROW_INDEX) {
+        return Err(format!("Column {ROW_INDEX} is reserved for pagination").into())
+    }
+
+    let page_size = page_query.page_size.unwrap_or(100);
+    if page_size == 0 || page_size >= IdxSize::MAX as u64 {
+        return Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("Invalid page_size"))))
+    }
+
+    let mut keys: Vec<String> = by;
+    keys.push(ROW_INDEX.to_string());
+    let mut keys_descending: Vec<bool> = descending;
+    keys_descending.push(false);
+
+    lf = lf.with_row_index(ROW_INDEX, None);
+    if let Some(r#where) = &query.r#where {
+        lf = lf.filter(predicate(r#where, &schema)?);
+    }
+    let keys_schema = lf.collect_schema()?;
+
+    let count: Option<u64> = if page_query.count.unwrap_or(false) {
+        let counted = lf.clone().select([len()]).collect()?;
+        Some(counted.column("len")?.get(0)?.extract::<u64>().unwrap_or(0))
+    } else {
+        None
+    };
+
+    if let Some(token) = &page_query.cursor {
+        let cursor = decode_cursor(token)?;
+        if cursor.order_by != keys || cursor.descending != keys_descending || cursor.after.len() != keys.len() {
+            return Err(Box::new(PolarsError::InvalidOperation(ErrString::new_static("Cursor does not match order_by"))))
+        }
+
+        // (k1 > v1) or (k1 == v1 and k2 > v2) or ...
+        let mut after: Option<Expr> = None;
+        let mut equal: Option<Expr> = None;
+        for ((key, value), desc) in keys.iter().zip(cursor.after.iter()).zip(keys_descending.iter()) {
+            let value = cursor_literal(value, keys_schema.try_get(key)?)?;
+            let beyond = if *desc { col(key).lt(value.clone()) } else { col(key).gt(value.clone()) };
+            let term = match equal.clone() {
+                Some(equal) => equal.and(beyond),
+                None => beyond
+            };
+            after = Some(match after {
+                Some(after) => after.or(term),
+                None => term
+            });
+            equal = Some(match equal {
+                Some(equal) => equal.and(col(key).eq(value)),
+                None => col(key).eq(value)
+            });
+        }
+        if let Some(after) = after {
+            lf = lf.filter(after);
+        }
+    }
+
+    // One extra row tells whether a next page exists
+    let mut df = lf
+        .sort(
+            keys.clone(),
+            SortMultipleOptions::default().with_order_descending_multi(keys_descending.clone())
+        )
+        .limit((page_size + 1) as IdxSize)
+        .collect()?;
+
+    let next_cursor: Option<String> = if df.height() as u64 > page_size {
+        df = df.head(Some(page_size as usize));
+        let cursor = Cursor {
+            after: cursor_values(&df, &keys, df.height() - 1)?,
+            order_by: keys,
+            descending: keys_descending
+        };
+        Some(encode_cursor(&cursor)?)
+    } else {
+        None
+    };
+
+    df = df.drop(ROW_INDEX)?;
+
+    if let Some(columns) = &query.columns {
+        df = project(df.lazy(), columns, &schema)?.collect()?;
+    }
+
+    Ok(Page { df, next_cursor, count })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame() -> DataFrame {
+        df!(
+            "id" => [1i64, 2, 3, 4],
+            "score" => [0.5f64, 1.5, 2.5, 3.5],
+            "name" => [Some("a,b"), Some("c"), None, Some("d")],
+            "active" => [true, false, true, false]
+        ).unwrap()
+    }
+
+    fn filtered(r#where: &str) -> Result<Vec<i64>, Box<dyn std::error::Error>> {
+        let df = frame();
+        let predicate = predicate(r#where, &df.schema())?;
+        let filtered = df.lazy().filter(predicate).collect()?;
+        Ok(filtered.column("id")?.i64()?.into_no_null_iter().collect())
+    }
+
+    #[test]
+    fn split_unquoted_keeps_quoted_commas() {
+        assert_eq!(split_unquoted("id>1, name='a,b' ,"), vec!["id>1", "name='a,b'"]);
+        assert_eq!(split_unquoted("name=\"x,'y\",id=2"), vec!["name=\"x,'y\"", "id=2"]);
+        assert!(split_unquoted(" , ").is_empty());
+    }
+
+    #[test]
+    fn where_compiles_every_operator() {
+        assert_eq!(filtered("id>=3").unwrap(), vec![3, 4]);
+        assert_eq!(filtered("id<=2").unwrap(), vec![1, 2]);
+        assert_eq!(filtered("id>3").unwrap(), vec![4]);
+        assert_eq!(filtered("id<2").unwrap(), vec![1]);
+        assert_eq!(filtered("id!=2").unwrap(), vec![1, 3, 4]);
+        assert_eq!(filtered("id==2").unwrap(), vec![2]);
+        assert_eq!(filtered("id=2").unwrap(), vec![2]);
+    }
+
+    #[test]
+    fn where_combines_conditions_with_and() {
+        assert_eq!(filtered("score>1, active=true").unwrap(), vec![3]);
+        assert_eq!(filtered("name='a,b', id<3").unwrap(), vec![1]);
+        assert_eq!(filtered("name==null").unwrap(), vec![3]);
+        assert_eq!(filtered("name!=null, score>=1.5").unwrap(), vec![2, 4]);
+    }
+
+    #[test]
+    fn where_rejects_malformed_conditions() {
+        assert!(filtered("").is_err());
+        assert!(filtered(" , ").is_err());
+        assert!(filtered("id").is_err());
+        assert!(filtered("missing=1").is_err());
+        assert!(filtered("id=one").is_err());
+        assert!(filtered("active=yes").is_err());
+        assert!(filtered("id>null").is_err());
+    }
+
+    fn no_query() -> FeedQuery {
+        FeedQuery { columns: None, r#where: None, sort: None, limit: None, offset: None }
+    }
+
+    fn page(
+        df: &DataFrame,
+        query: &FeedQuery,
+        order_by: &str,
+        cursor: Option<String>
+    ) -> Result<Page, Box<dyn std::error::Error>> {
+        let page_query = PageQuery {
+            order_by: Some(order_by.to_string()),
+            page_size: Some(2),
+            cursor,
+            count: Some(true)
+        };
+        paginate(df.clone().lazy(), query, &page_query)
+    }
+
+    fn ids(page: &Page) -> Vec<i64> {
+        page.df.column("id").unwrap().i64().unwrap().into_no_null_iter().collect()
+    }
+
+    #[test]
+    fn cursor_round_trips() {
+        let cursor = Cursor {
+            order_by: vec!["id".to_string(), "name".to_string()],
+            descending: vec![false, true],
+            after: vec![Value::from(-3), Value::from("a,b"), Value::from(1.5), Value::from(true)]
+        };
+        let decoded = decode_cursor(&encode_cursor(&cursor).unwrap()).unwrap();
+        assert_eq!(decoded.order_by, cursor.order_by);
+        assert_eq!(decoded.descending, cursor.descending);
+        assert_eq!(decoded.after, cursor.after);
+
+        assert!(decode_cursor("not a cursor").is_err());
+        assert!(decode_cursor(&URL_SAFE_NO_PAD.encode("{}")).is_err());
+    }
+
+    #[test]
+    fn pages_follow_the_cursor_up_to_the_last_page() {
+        let df = frame();
+
+        let first = page(&df, &no_query(), "-score", None).unwrap();
+        assert_eq!(ids(&first), vec![4, 3]);
+        assert_eq!(first.count, Some(4));
+
+        let last = page(&df, &no_query(), "-score", first.next_cursor.clone()).unwrap();
+        assert_eq!(ids(&last), vec![2, 1]);
+        assert_eq!(last.next_cursor, None);
+
+        assert!(page(&df, &no_query(), "id", first.next_cursor).is_err());
+    }
+
+    #[test]
+    fn pages_keep_rows_sharing_a_boundary_key() {
+        let df = df!(
+            "id" => [1i64, 2, 3, 4, 5],
+            "group" => [1i64, 1, 1, 2, 2]
+        ).unwrap();
+
+        let mut seen: Vec<i64> = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let page = page(&df, &no_query(), "group", cursor).unwrap();
+            seen.extend(ids(&page));
+            assert!(!page.df.schema().contains(ROW_INDEX));
+            cursor = page.next_cursor;
+            if cursor.is_none() {
+                break;
+            }
+        }
+        assert_eq!(seen, vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn empty_page_has_no_cursor() {
+        let query = FeedQuery { r#where: Some("id>10".to_string()), ..no_query() };
+        let empty = page(&frame(), &query, "id", None).unwrap();
+        assert_eq!(empty.df.height(), 0);
+        assert_eq!(empty.next_cursor, None);
+        assert_eq!(empty.count, Some(0));
+    }
+
+    #[test]
+    fn cursor_rejects_unsupported_keys() {
+        let mut df = frame();
+        df.apply("name", |name| name.cast(&DataType::Categorical(None, Default::default())).unwrap()).unwrap();
+        assert!(page(&df, &no_query(), "name,id", None).is_err());
+
+        let nan = df!(
+            "id" => [1i64, 2, 3],
+            "score" => [1.0, f64::NAN, f64::NAN]
+        ).unwrap();
+        assert!(page(&nan, &no_query(), "score", None).is_err());
+        assert!(page(&nan, &no_query(), "-score,id", None).is_err());
+        assert!(page(&nan, &no_query(), "id", None).is_ok());
+    }
+}